@@ -0,0 +1,274 @@
+//! Derive macro for the `PasetoClaims` trait of [`paseto_maker`](https://docs.rs/paseto_maker).
+//!
+//! Use it through the `derive` feature of `paseto_maker` rather than depending on this crate directly.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Field, Fields, GenericArgument, Ident,
+    LitStr, PathArguments, Type,
+};
+
+/// Derives `paseto_maker::PasetoClaims` for a struct with named fields.
+///
+/// Every field becomes a claim named after the field. `#[paseto(rename = "...")]` changes the claim name and
+/// `#[paseto(exp)]`, `nbf`, `iat`, `sub`, `iss`, `aud` or `jti` maps the field to a reserved claim. Reserved
+/// time claims must be `DateTime<Utc>` and all other reserved claims `String`, which is checked at compile
+/// time. `Option` fields are left out when `None` and become `None` when the claim is missing.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{DateTime, Duration, Utc};
+/// use paseto_maker::{Maker, PasetoClaims};
+///
+/// #[derive(PasetoClaims)]
+/// struct Session {
+///     #[paseto(sub)]
+///     user_id: String,
+///     #[paseto(exp)]
+///     expires_at: DateTime<Utc>,
+///     #[paseto(jti)]
+///     token_id: Option<String>,
+///     role: String,
+/// }
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).unwrap();
+/// let session = Session {
+///     user_id: "1234567890".to_string(),
+///     expires_at: Utc::now() + Duration::hours(1),
+///     token_id: None,
+///     role: "admin".to_string(),
+/// };
+///
+/// let token = maker.create_token(&session.to_claims().unwrap()).unwrap();
+/// let session = Session::from_claims(&maker.verify_token(&token).unwrap()).unwrap();
+/// assert_eq!(session.user_id, "1234567890");
+/// ```
+///
+/// Reserved claims of the wrong type are rejected:
+///
+/// ```compile_fail
+/// use paseto_maker::PasetoClaims;
+///
+/// #[derive(PasetoClaims)]
+/// struct Session {
+///     #[paseto(exp)]
+///     expires_at: i64,
+/// }
+/// ```
+#[proc_macro_derive(PasetoClaims, attributes(paseto))]
+pub fn derive_paseto_claims(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A reserved claim: its key, the builder method that sets it and whether it holds a date.
+struct Reserved {
+    key: &'static str,
+    setter: &'static str,
+    time: bool,
+}
+
+const RESERVED: [Reserved; 7] = [
+    Reserved {
+        key: "exp",
+        setter: "with_expiration_time",
+        time: true,
+    },
+    Reserved {
+        key: "nbf",
+        setter: "with_not_before_time",
+        time: true,
+    },
+    Reserved {
+        key: "iat",
+        setter: "with_issued_at_time",
+        time: true,
+    },
+    Reserved {
+        key: "sub",
+        setter: "with_subject",
+        time: false,
+    },
+    Reserved {
+        key: "iss",
+        setter: "with_issuer",
+        time: false,
+    },
+    Reserved {
+        key: "aud",
+        setter: "with_audience",
+        time: false,
+    },
+    Reserved {
+        key: "jti",
+        setter: "with_token_identifier",
+        time: false,
+    },
+];
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "PasetoClaims can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "PasetoClaims can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut to_claims = Vec::new();
+    let mut from_claims = Vec::new();
+    for field in &fields.named {
+        let (to, from) = expand_field(field)?;
+        to_claims.push(to);
+        from_claims.push(from);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::paseto_maker::PasetoClaims for #name #ty_generics #where_clause {
+            fn to_claims(
+                &self,
+            ) -> ::std::result::Result<::paseto_maker::Claims, ::paseto_maker::errors::ClaimError> {
+                #[allow(unused_mut)]
+                let mut claims = ::paseto_maker::Claims::new();
+                #(#to_claims)*
+                ::std::result::Result::Ok(claims)
+            }
+
+            fn from_claims(
+                claims: &::paseto_maker::Claims,
+            ) -> ::std::result::Result<Self, ::paseto_maker::errors::ClaimError> {
+                ::std::result::Result::Ok(Self {
+                    #(#from_claims,)*
+                })
+            }
+        }
+    })
+}
+
+/// Generates the statement that writes a field into `claims` and the initializer that reads it back.
+fn expand_field(field: &Field) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let ident = field.ident.as_ref().expect("named fields have an ident");
+    let key = claim_key(field)?;
+    let (ty, optional) = match option_inner(&field.ty) {
+        Some(inner) => (inner, true),
+        None => (&field.ty, false),
+    };
+
+    let write = match RESERVED.iter().find(|reserved| reserved.key == key) {
+        Some(reserved) => {
+            let setter = Ident::new(reserved.setter, Span::call_site());
+            let (expected, value) = if reserved.time {
+                (
+                    quote!(
+                        ::paseto_maker::__private::chrono::DateTime<
+                            ::paseto_maker::__private::chrono::Utc,
+                        >
+                    ),
+                    quote!(*value),
+                )
+            } else {
+                (quote!(::std::string::String), quote!(value))
+            };
+            let check = quote_spanned!(ty.span()=> let value: &#expected = value;);
+            quote! {
+                #check
+                claims = claims.#setter(#value);
+            }
+        }
+        None => quote! {
+            claims.set_claim(#key, value)?;
+        },
+    };
+
+    let to = if optional {
+        quote! {
+            if let ::std::option::Option::Some(value) = &self.#ident {
+                #write
+            }
+        }
+    } else {
+        quote! {
+            {
+                let value = &self.#ident;
+                #write
+            }
+        }
+    };
+
+    let from = if optional {
+        quote!(#ident: ::paseto_maker::__private::claim(claims, #key)?)
+    } else {
+        quote! {
+            #ident: ::paseto_maker::__private::claim(claims, #key)?.ok_or_else(|| {
+                ::paseto_maker::errors::ClaimError::MissingClaim(#key.to_string())
+            })?
+        }
+    };
+    Ok((to, from))
+}
+
+/// Returns the claim name of a field from its `#[paseto(...)]` attribute, defaulting to the field name.
+fn claim_key(field: &Field) -> syn::Result<String> {
+    let mut key = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("paseto"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let value = if meta.path.is_ident("rename") {
+                meta.value()?.parse::<LitStr>()?.value()
+            } else if let Some(reserved) = RESERVED
+                .iter()
+                .find(|reserved| meta.path.is_ident(reserved.key))
+            {
+                reserved.key.to_string()
+            } else {
+                return Err(meta.error(
+                    "expected `rename = \"...\"` or one of `exp`, `nbf`, `iat`, `sub`, `iss`, `aud`, `jti`",
+                ));
+            };
+            if key.replace(value).is_some() {
+                return Err(meta.error("a field can only be mapped to one claim"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(key.unwrap_or_else(|| {
+        field
+            .ident
+            .as_ref()
+            .expect("named fields have an ident")
+            .to_string()
+    }))
+}
+
+/// Returns `T` if `ty` is written as `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
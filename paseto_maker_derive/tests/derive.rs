@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+use paseto_maker::{errors::ClaimError, Claims, Maker, PasetoClaims};
+
+#[derive(Debug, PartialEq, PasetoClaims)]
+struct Session {
+    #[paseto(sub)]
+    user_id: String,
+    #[paseto(iss)]
+    issuer: String,
+    #[paseto(exp)]
+    expires_at: DateTime<Utc>,
+    #[paseto(iat)]
+    issued_at: Option<DateTime<Utc>>,
+    #[paseto(jti)]
+    token_id: Option<String>,
+    #[paseto(rename = "scp")]
+    scopes: Vec<String>,
+    admin: bool,
+}
+
+fn session() -> Session {
+    let now = DateTime::parse_from_rfc3339("2024-09-18T03:42:15+00:00")
+        .unwrap()
+        .with_timezone(&Utc);
+    Session {
+        user_id: "1234567890".to_string(),
+        issuer: "auth.example.com".to_string(),
+        expires_at: now + Duration::hours(1),
+        issued_at: Some(now),
+        token_id: None,
+        scopes: vec!["read".to_string(), "write".to_string()],
+        admin: true,
+    }
+}
+
+#[test]
+fn test_to_claims() {
+    let claims = session().to_claims().unwrap();
+
+    assert_eq!(claims.get_subject(), Some("1234567890".to_string()));
+    assert_eq!(claims.get_issuer(), Some("auth.example.com".to_string()));
+    assert_eq!(
+        claims.get_claim::<String>("exp"),
+        Some("2024-09-18T04:42:15+00:00".to_string())
+    );
+    assert_eq!(claims.get_issued_at(), session().issued_at);
+    assert_eq!(claims.get_token_identifier(), None);
+    assert_eq!(
+        claims.get_claim::<Vec<String>>("scp"),
+        Some(vec!["read".to_string(), "write".to_string()])
+    );
+    assert_eq!(claims.get_claim::<bool>("admin"), Some(true));
+}
+
+#[test]
+fn test_round_trip() {
+    let claims = session().to_claims().unwrap();
+    assert_eq!(Session::from_claims(&claims).unwrap(), session());
+}
+
+#[test]
+fn test_token_round_trip() {
+    let (priv_key, _) = Maker::new_keypair();
+    let maker = Maker::new(&priv_key).expect("failed to create maker");
+    let session = Session {
+        expires_at: Utc::now() + Duration::hours(1),
+        issued_at: None,
+        token_id: Some("token_id".to_string()),
+        ..session()
+    };
+
+    let token = maker
+        .create_token(&session.to_claims().unwrap())
+        .expect("failed to generate token");
+    let claims = maker.verify_token(&token).expect("failed to verify token");
+    let got = Session::from_claims(&claims).unwrap();
+
+    assert_eq!(got.user_id, session.user_id);
+    assert_eq!(got.token_id, session.token_id);
+    assert_eq!(got.expires_at.timestamp(), session.expires_at.timestamp());
+    assert_eq!(got.scopes, session.scopes);
+}
+
+#[test]
+fn test_from_claims_errors() {
+    let claims = Claims::new().with_subject("1234567890");
+    assert!(matches!(
+        Session::from_claims(&claims),
+        Err(ClaimError::MissingClaim(key)) if key == "iss"
+    ));
+
+    let mut claims = session().to_claims().unwrap();
+    claims.set_claim("admin", "yes").unwrap();
+    assert!(matches!(
+        Session::from_claims(&claims),
+        Err(ClaimError::SerializationError(_))
+    ));
+}
@@ -0,0 +1,523 @@
+//! Command-line interface for generating keys and creating, verifying and inspecting V4 PASETO tokens.
+//!
+//! Requires the `cli` feature, e.g. `cargo install paseto_maker --features cli`.
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use chrono::Duration;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use paseto_maker::{
+    purpose::{Local, Public},
+    version::V4,
+    Claims, Footer, Maker, Token, Verifier,
+};
+use serde_json::{json, Value};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "paseto-maker",
+    version,
+    about = "Generate keys and create, verify and inspect V4 PASETO tokens"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generates a new secret key for public tokens or a new shared key for local tokens.
+    Keygen(KeygenArgs),
+    /// Creates a token from claims read as JSON from stdin or given as flags.
+    Sign(SignArgs),
+    /// Verifies a token and prints its claims as JSON.
+    Verify(VerifyArgs),
+    /// Decodes the header, payload and footer of a public token without verifying it.
+    Inspect {
+        /// The token to inspect. Read from stdin if omitted.
+        token: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Purpose {
+    Public,
+    Local,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum KeyFormat {
+    Raw,
+    Hex,
+    Paserk,
+}
+
+#[derive(Debug, Args)]
+struct KeygenArgs {
+    #[arg(long, value_enum, default_value_t = Purpose::Public)]
+    purpose: Purpose,
+    #[arg(long, value_enum, default_value_t = KeyFormat::Paserk)]
+    format: KeyFormat,
+    /// File to write the secret or shared key to. Written to stdout if omitted.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// File to write the public key of a `public` key pair to.
+    #[arg(long)]
+    public_output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct KeyArgs {
+    /// File containing the key as a PASERK, as hex or as raw bytes.
+    #[arg(long, short)]
+    key: PathBuf,
+    /// Whether a 32 byte hex or raw key is a public or a shared local key. PASERKs carry their own purpose.
+    #[arg(long, value_enum, default_value_t = Purpose::Public)]
+    purpose: Purpose,
+}
+
+#[derive(Debug, Args)]
+struct SignArgs {
+    #[command(flatten)]
+    key: KeyArgs,
+    /// Reads the claims as a JSON object from stdin. Flags take precedence over claims read from stdin.
+    #[arg(long)]
+    stdin: bool,
+    #[arg(long)]
+    subject: Option<String>,
+    #[arg(long)]
+    issuer: Option<String>,
+    #[arg(long)]
+    audience: Option<String>,
+    #[arg(long)]
+    token_identifier: Option<String>,
+    /// Lifetime of the token in seconds. Tokens expire after one hour by default.
+    #[arg(long, value_name = "SECONDS")]
+    expires_in: Option<i64>,
+    /// An additional claim. Values that are not valid JSON are used as strings.
+    #[arg(long = "claim", value_name = "KEY=VALUE")]
+    claims: Vec<String>,
+    /// An unencrypted footer to attach to the token.
+    #[arg(long)]
+    footer: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+    #[command(flatten)]
+    key: KeyArgs,
+    /// The token to verify. Read from stdin if omitted.
+    token: Option<String>,
+    /// Accepted clock skew in seconds for the time-based claims.
+    #[arg(long, value_name = "SECONDS")]
+    leeway: Option<i64>,
+    /// Rejects the token unless its footer is exactly this value.
+    #[arg(long)]
+    footer: Option<String>,
+}
+
+/// A key read from a file, with the type that can sign or verify tokens with it.
+enum Key {
+    Secret(Box<Maker<V4, Public>>),
+    Public(Box<Verifier<V4, Public>>),
+    Local(Box<Maker<V4, Local>>),
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> CliResult<()> {
+    match cli.command {
+        Command::Keygen(args) => keygen(&args),
+        Command::Sign(args) => {
+            let token = sign(&args, read_key(&args.key)?)?;
+            println!("{token}");
+            Ok(())
+        }
+        Command::Verify(args) => {
+            let token = token_or_stdin(args.token.as_deref())?;
+            let claims = verify(&args, read_key(&args.key)?, &token)?;
+            println!("{}", serde_json::to_string_pretty(&claims)?);
+            Ok(())
+        }
+        Command::Inspect { token } => {
+            let token = token_or_stdin(token.as_deref())?;
+            println!("{}", serde_json::to_string_pretty(&inspect(&token)?)?);
+            Ok(())
+        }
+    }
+}
+
+fn keygen(args: &KeygenArgs) -> CliResult<()> {
+    match args.purpose {
+        Purpose::Public => {
+            let (secret_key, public_key) = Maker::new_keypair();
+            let maker = Maker::new(&secret_key)?;
+            write_key(
+                args.output.as_deref(),
+                &encode_key(args.format, &secret_key, &maker.to_paserk_secret()),
+                true,
+            )?;
+            if let Some(path) = &args.public_output {
+                write_key(
+                    Some(path),
+                    &encode_key(args.format, &public_key, &maker.to_paserk_public()),
+                    false,
+                )?;
+            }
+        }
+        Purpose::Local => {
+            if args.public_output.is_some() {
+                return Err("local keys have no public key".into());
+            }
+            let maker = Maker::new_local(&Maker::new_local_key());
+            write_key(
+                args.output.as_deref(),
+                &encode_key(args.format, maker.key_as_bytes(), &maker.to_paserk_local()),
+                true,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn encode_key(format: KeyFormat, key: &[u8], paserk: &str) -> Vec<u8> {
+    match format {
+        KeyFormat::Raw => key.to_vec(),
+        KeyFormat::Hex => format!("{}\n", encode_hex(key)).into_bytes(),
+        KeyFormat::Paserk => format!("{paserk}\n").into_bytes(),
+    }
+}
+
+/// Writes `key` to `path`, or to stdout if omitted. Secret and local key files are only readable by their owner
+/// on Unix.
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn write_key(path: Option<&Path>, key: &[u8], secret: bool) -> CliResult<()> {
+    let Some(path) = path else {
+        io::stdout().write_all(key)?;
+        return Ok(());
+    };
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if secret {
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    if secret {
+        // `mode` only applies to new files, so an existing file is restricted as well.
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(key)?;
+    Ok(())
+}
+
+fn read_key(args: &KeyArgs) -> CliResult<Key> {
+    let bytes = fs::read(&args.key)?;
+    let text = std::str::from_utf8(&bytes).map(str::trim).ok();
+
+    if let Some(paserk) = text.filter(|text| text.starts_with("k4.")) {
+        return Ok(match paserk.split('.').nth(1) {
+            Some("secret") => Key::Secret(Box::new(Maker::from_paserk(paserk)?)),
+            Some("public") => Key::Public(Box::new(Verifier::from_paserk(paserk)?)),
+            Some("local") => Key::Local(Box::new(Maker::from_paserk_local(paserk)?)),
+            _ => return Err("unsupported PASERK type".into()),
+        });
+    }
+
+    let key = match text.and_then(decode_hex) {
+        Some(key) => key,
+        None => bytes,
+    };
+    if let Ok(secret_key) = <[u8; 64]>::try_from(key.as_slice()) {
+        return Ok(Key::Secret(Box::new(Maker::new(&secret_key)?)));
+    }
+    let key = <[u8; 32]>::try_from(key.as_slice())
+        .map_err(|_| format!("expected a 32 or 64 byte key, got {} bytes", key.len()))?;
+    Ok(match args.purpose {
+        Purpose::Public => Key::Public(Box::new(Verifier::new(&key)?)),
+        Purpose::Local => Key::Local(Box::new(Maker::new_local(&key))),
+    })
+}
+
+fn sign(args: &SignArgs, key: Key) -> CliResult<String> {
+    let claims = sign_claims(args, &mut io::stdin())?;
+    let footer = args.footer.as_deref().map(Footer::parse);
+    let token = match (key, footer) {
+        (Key::Secret(maker), None) => maker.create_token(&claims)?.into_string(),
+        (Key::Secret(maker), Some(footer)) => maker
+            .create_token_with_footer(&claims, &footer)?
+            .into_string(),
+        (Key::Local(maker), None) => maker.create_token(&claims)?.into_string(),
+        (Key::Local(maker), Some(footer)) => maker
+            .create_token_with_footer(&claims, &footer)?
+            .into_string(),
+        (Key::Public(_), _) => return Err("a public key cannot sign tokens".into()),
+    };
+    Ok(token)
+}
+
+fn sign_claims<R: Read>(args: &SignArgs, stdin: &mut R) -> CliResult<Claims> {
+    let mut claims = if args.stdin {
+        let mut input = String::new();
+        stdin.read_to_string(&mut input)?;
+        Claims::try_from(serde_json::from_str::<Value>(&input)?)?
+    } else {
+        Claims::new()
+    };
+
+    for claim in &args.claims {
+        let (key, value) = claim
+            .split_once('=')
+            .ok_or_else(|| format!("expected a claim as KEY=VALUE, got '{claim}'"))?;
+        let value =
+            serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        claims.set_claim(key, value)?;
+    }
+    if let Some(subject) = &args.subject {
+        claims = claims.with_subject(subject);
+    }
+    if let Some(issuer) = &args.issuer {
+        claims = claims.with_issuer(issuer);
+    }
+    if let Some(audience) = &args.audience {
+        claims = claims.with_audience(audience);
+    }
+    if let Some(token_identifier) = &args.token_identifier {
+        claims = claims.with_token_identifier(token_identifier);
+    }
+    if let Some(seconds) = args.expires_in {
+        claims = claims.expires_in(Duration::seconds(seconds));
+    }
+    Ok(claims)
+}
+
+fn verify(args: &VerifyArgs, key: Key, token: &str) -> CliResult<Claims> {
+    let leeway = Duration::seconds(args.leeway.unwrap_or_default());
+    let (claims, footer) = match key {
+        Key::Secret(maker) => maker.with_leeway(leeway).verify_token_with_footer(token)?,
+        Key::Public(verifier) => verifier
+            .with_leeway(leeway)
+            .verify_token_with_footer(token)?,
+        Key::Local(maker) => maker.with_leeway(leeway).verify_token_with_footer(token)?,
+    };
+    if let Some(expected) = &args.footer {
+        if footer.as_ref() != Some(&Footer::parse(expected)) {
+            return Err("token footer does not match".into());
+        }
+    }
+    Ok(claims)
+}
+
+/// Decodes a `V4.public` token without verifying it.
+fn inspect(token: &str) -> CliResult<Value> {
+    let token = Token::parse(token)?;
+    let (claims, footer) = token.inspect_unverified()?;
+    let footer = match footer {
+        Some(Footer::Json(map)) => Value::Object(map),
+        Some(Footer::Raw(raw)) => Value::String(raw),
+        None => Value::Null,
+    };
+    Ok(json!({
+        "header": format!("{}.{}.", token.version(), token.purpose()),
+        "payload": claims,
+        "footer": footer,
+    }))
+}
+
+fn token_or_stdin(token: Option<&str>) -> CliResult<String> {
+    match token {
+        Some(token) if token != "-" => Ok(token.trim().to_string()),
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input.trim().to_string())
+        }
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key_file(contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("paseto-maker-{}.key", uuid::Uuid::new_v4()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn sign_args(key: PathBuf) -> SignArgs {
+        SignArgs {
+            key: KeyArgs {
+                key,
+                purpose: Purpose::Public,
+            },
+            stdin: false,
+            subject: Some("user".to_string()),
+            issuer: None,
+            audience: None,
+            token_identifier: None,
+            expires_in: None,
+            claims: vec!["role=admin".to_string(), "level=3".to_string()],
+            footer: Some(r#"{"kid":"key-1"}"#.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_hex() {
+        let key: Vec<u8> = (0..32).collect();
+        assert_eq!(decode_hex(&encode_hex(&key)), Some(key));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn test_sign_verify_inspect() {
+        let (secret_key, public_key) = Maker::new_keypair();
+        let secret_path = key_file(format!("{}\n", encode_hex(&secret_key)).as_bytes());
+        let public_path = key_file(&public_key);
+
+        let args = sign_args(secret_path.clone());
+        let token = sign(&args, read_key(&args.key).unwrap()).unwrap();
+
+        let args = VerifyArgs {
+            key: KeyArgs {
+                key: public_path.clone(),
+                purpose: Purpose::Public,
+            },
+            token: None,
+            leeway: None,
+            footer: Some(r#"{"kid":"key-1"}"#.to_string()),
+        };
+        let claims = verify(&args, read_key(&args.key).unwrap(), &token).unwrap();
+        assert_eq!(claims.get_subject(), Some("user".to_string()));
+        assert_eq!(
+            claims.get_claim::<String>("role"),
+            Some("admin".to_string())
+        );
+        assert_eq!(claims.get_claim::<i64>("level"), Some(3));
+
+        let inspected = inspect(&token).unwrap();
+        assert_eq!(inspected["header"], "v4.public.");
+        assert_eq!(inspected["payload"]["sub"], "user");
+        assert_eq!(inspected["footer"]["kid"], "key-1");
+
+        let args = VerifyArgs {
+            footer: Some("other".to_string()),
+            ..args
+        };
+        assert!(verify(&args, read_key(&args.key).unwrap(), &token).is_err());
+
+        let args = sign_args(public_path.clone());
+        assert!(sign(&args, read_key(&args.key).unwrap()).is_err());
+
+        fs::remove_file(secret_path).unwrap();
+        fs::remove_file(public_path).unwrap();
+    }
+
+    #[test]
+    fn test_sign_claims_from_stdin() {
+        let args = SignArgs {
+            stdin: true,
+            ..sign_args(PathBuf::new())
+        };
+        let claims =
+            sign_claims(&args, &mut r#"{"sub":"other","scope":"read"}"#.as_bytes()).unwrap();
+        assert_eq!(claims.get_subject(), Some("user".to_string()));
+        assert_eq!(
+            claims.get_claim::<String>("scope"),
+            Some("read".to_string())
+        );
+
+        assert!(sign_claims(&args, &mut "[]".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_local_key() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let path = key_file(maker.to_paserk_local().as_bytes());
+        let args = KeyArgs {
+            key: path.clone(),
+            purpose: Purpose::Public,
+        };
+        let Key::Local(local) = read_key(&args).unwrap() else {
+            panic!("expected a local key");
+        };
+        assert_eq!(local.key_as_bytes(), maker.key_as_bytes());
+
+        fs::write(&path, [0u8; 16]).unwrap();
+        assert!(read_key(&args).is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_keygen_permissions() {
+        let dir = std::env::temp_dir();
+        let output = dir.join(format!("paseto-maker-{}.key", uuid::Uuid::new_v4()));
+        let public_output = dir.join(format!("paseto-maker-{}.pub", uuid::Uuid::new_v4()));
+        fs::write(&output, b"").unwrap();
+        fs::set_permissions(&output, fs::Permissions::from_mode(0o644)).unwrap();
+
+        keygen(&KeygenArgs {
+            purpose: Purpose::Public,
+            format: KeyFormat::Paserk,
+            output: Some(output.clone()),
+            public_output: Some(public_output.clone()),
+        })
+        .unwrap();
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&output), 0o600);
+        assert!(matches!(
+            read_key(&KeyArgs {
+                key: output.clone(),
+                purpose: Purpose::Public,
+            })
+            .unwrap(),
+            Key::Secret(_)
+        ));
+        fs::remove_file(&output).unwrap();
+        fs::remove_file(public_output).unwrap();
+
+        keygen(&KeygenArgs {
+            purpose: Purpose::Local,
+            format: KeyFormat::Hex,
+            output: Some(output.clone()),
+            public_output: None,
+        })
+        .unwrap();
+        assert_eq!(mode(&output), 0o600);
+        fs::remove_file(output).unwrap();
+    }
+}
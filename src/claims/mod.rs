@@ -1,7 +1,7 @@
 use crate::errors::ClaimError;
 
-use chrono::{DateTime, Utc};
-use serde::{de::DeserializeOwned, Serialize};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::{
     collections::BTreeMap,
@@ -24,13 +24,14 @@ pub mod reserved;
 ///     .with_subject("1234567890")
 ///     .with_issuer("issuer")
 ///     .with_audience("audience")
-///     .with_expiration("2023-10-01T00:00:00+00:00")
-///     .with_not_before("2023-09-01T00:00:00+00:00")
-///     .with_issued_at("2023-09-01T00:00:00+00:00")
+///     .with_expiration("2023-10-01T00:00:00+00:00")?
+///     .with_not_before("2023-09-01T00:00:00+00:00")?
+///     .with_issued_at("2023-09-01T00:00:00+00:00")?
 ///     .with_token_identifier("token_id");
 ///
 /// let subject: Option<String> = claims.get_subject();
 /// assert_eq!(subject, Some("1234567890".to_string()));
+/// # Ok::<(), paseto_maker::errors::ClaimError>(())
 /// ```
 ///
 /// # Methods
@@ -39,11 +40,18 @@ pub mod reserved;
 /// - `with_subject`: Adds a subject claim.
 /// - `with_issuer`: Adds an issuer claim.
 /// - `with_audience`: Adds an audience claim.
-/// - `with_expiration`: Adds an expiration claim.
-/// - `with_not_before`: Adds a not-before claim.
-/// - `with_issued_at`: Adds an issued-at claim.
+/// - `with_expiration`: Adds an expiration claim from an RFC 3339 date.
+/// - `with_not_before`: Adds a not-before claim from an RFC 3339 date.
+/// - `with_issued_at`: Adds an issued-at claim from an RFC 3339 date.
 /// - `with_token_identifier`: Adds a token identifier claim.
+/// - `with_expiration_time`: Adds an expiration claim from a `DateTime<Utc>`.
+/// - `with_not_before_time`: Adds a not-before claim from a `DateTime<Utc>`.
+/// - `with_issued_at_time`: Adds an issued-at claim from a `DateTime<Utc>`.
+/// - `expires_in`: Adds an expiration claim relative to now.
+/// - `valid_from_now`: Adds a not-before claim set to now.
+/// - `issued_now`: Adds an issued-at claim set to now.
 /// - `set_claim`: Sets a custom claim with a specified key and value.
+/// - `remove_claim`: Removes a claim by key and returns its value.
 /// - `get_claim`: Retrieves a claim by key and attempts to deserialize it into the specified type.
 /// - `get_subject`: Retrieves the subject claim.
 /// - `get_issuer`: Retrieves the issuer claim.
@@ -53,10 +61,18 @@ pub mod reserved;
 /// - `get_issued_at`: Retrieves the issued-at claim.
 /// - `get_token_identifier`: Retrieves the token identifier claim.
 /// - `iter`: Returns an iterator over the claims.
+/// - `from_serializable`: Creates claims from any serializable struct or map.
+/// - `deserialize_into`: Deserializes the claims into a user-defined type.
 ///
 /// # Errors
 ///
-/// - `set_claim` will return an error if the value cannot be serialized or is null.
+/// - `set_claim` will return an error if the value cannot be serialized or is null, or if a reserved
+///   claim has the wrong type. `iss`, `sub`, `aud` and `jti` must be strings, `exp`, `nbf` and `iat`
+///   must be RFC 3339 dates.
+/// - `with_expiration`, `with_not_before` and `with_issued_at` will return an error if the value is not an
+///   RFC 3339 date.
+/// - `Claims::try_from(Value)`, `from_serializable` and `Deserialize` apply the same checks to every
+///   claim of a JSON object.
 /// # Examples
 ///
 /// ```
@@ -79,15 +95,15 @@ pub mod reserved;
 ///
 /// ```
 /// use paseto_maker::Claims;
-/// use chrono::{DateTime, Utc};
+/// use chrono::{DateTime, Duration, SecondsFormat, Utc};
 ///
 /// let claims = Claims::new()
 ///     .with_subject("1234567890")
 ///     .with_issuer("issuer")
 ///     .with_audience("audience")
-///     .with_expiration("2023-10-01T00:00:00+00:00")
-///     .with_not_before("2023-09-01T00:00:00+00:00")
-///     .with_issued_at("2023-09-01T00:00:00+00:00")
+///     .with_expiration("2023-10-01T00:00:00+00:00")?
+///     .with_not_before("2023-09-01T00:00:00+00:00")?
+///     .with_issued_at("2023-09-01T00:00:00+00:00")?
 ///     .with_token_identifier("token_id");
 ///
 /// let subject: Option<String> = claims.get_subject();
@@ -110,9 +126,30 @@ pub mod reserved;
 ///
 /// let token_identifier: Option<String> = claims.get_token_identifier();
 /// assert_eq!(token_identifier, Some("token_id".to_string()));
+/// # Ok::<(), paseto_maker::errors::ClaimError>(())
 /// ```
-
-#[derive(Debug, Default)]
+///
+/// Reserved claims map onto struct fields through serde renames:
+///
+/// ```
+/// use paseto_maker::Claims;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Session {
+///     #[serde(rename = "sub")]
+///     user_id: String,
+///     role: String,
+/// }
+///
+/// let session = Session { user_id: "1234567890".to_string(), role: "admin".to_string() };
+/// let claims = Claims::from_serializable(&session).unwrap();
+/// assert_eq!(claims.get_subject(), Some("1234567890".to_string()));
+///
+/// let session: Session = claims.deserialize_into().unwrap();
+/// assert_eq!(session.role, "admin");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Claims {
     claims: BTreeMap<Arc<str>, Value>,
 }
@@ -128,13 +165,30 @@ impl Display for Claims {
     }
 }
 
-impl From<Value> for Claims {
-    fn from(value: Value) -> Self {
-        let claims: BTreeMap<Arc<str>, Value> = match value {
-            Value::Object(map) => map.into_iter().map(|(k, v)| (Arc::from(k), v)).collect(),
-            _ => BTreeMap::new(),
+impl TryFrom<Value> for Claims {
+    type Error = ClaimError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let Value::Object(map) = value else {
+            return Err(ClaimError::InvalidValue);
         };
-        Self { claims }
+        let mut claims = Self::new();
+        for (key, value) in map {
+            claims.insert(&key, value)?;
+        }
+        Ok(claims)
+    }
+}
+
+impl Serialize for Claims {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.claims.iter().map(|(key, value)| (key.as_ref(), value)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Claims {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(Value::deserialize(deserializer)?).map_err(serde::de::Error::custom)
     }
 }
 
@@ -149,54 +203,77 @@ impl Claims {
     }
 
     #[must_use]
-    pub fn with_subject<T: AsRef<str>>(mut self, subject: T) -> Self {
-        self.claims
-            .insert(Arc::from(reserved::SUBJECT), subject.as_ref().into());
-        self
+    pub fn with_subject<T: AsRef<str>>(self, subject: T) -> Self {
+        self.with_string(reserved::SUBJECT, subject.as_ref())
     }
 
     #[must_use]
-    pub fn with_issuer<T: AsRef<str>>(mut self, issuer: T) -> Self {
-        self.claims
-            .insert(Arc::from(reserved::ISSUER), issuer.as_ref().into());
-        self
+    pub fn with_issuer<T: AsRef<str>>(self, issuer: T) -> Self {
+        self.with_string(reserved::ISSUER, issuer.as_ref())
     }
 
     #[must_use]
-    pub fn with_audience<T: AsRef<str>>(mut self, audience: T) -> Self {
-        self.claims
-            .insert(Arc::from(reserved::AUDIENCE), audience.as_ref().into());
-        self
+    pub fn with_audience<T: AsRef<str>>(self, audience: T) -> Self {
+        self.with_string(reserved::AUDIENCE, audience.as_ref())
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if `expiration` is not an RFC 3339 date.
+    pub fn with_expiration<T: AsRef<str>>(mut self, expiration: T) -> Result<Self, ClaimError> {
+        self.insert(reserved::EXPIRATION, expiration.as_ref().into())?;
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if `not_before` is not an RFC 3339 date.
+    pub fn with_not_before<T: AsRef<str>>(mut self, not_before: T) -> Result<Self, ClaimError> {
+        self.insert(reserved::NOT_BEFORE, not_before.as_ref().into())?;
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if `issued_at` is not an RFC 3339 date.
+    pub fn with_issued_at<T: AsRef<str>>(mut self, issued_at: T) -> Result<Self, ClaimError> {
+        self.insert(reserved::ISSUED_AT, issued_at.as_ref().into())?;
+        Ok(self)
     }
 
     #[must_use]
-    pub fn with_expiration<T: AsRef<str>>(mut self, expiration: T) -> Self {
-        self.claims
-            .insert(Arc::from(reserved::EXPIRATION), expiration.as_ref().into());
-        self
+    pub fn with_token_identifier<T: AsRef<str>>(self, token_identifier: T) -> Self {
+        self.with_string(reserved::TOKEN_IDENTIFIER, token_identifier.as_ref())
     }
 
     #[must_use]
-    pub fn with_not_before<T: AsRef<str>>(mut self, not_before: T) -> Self {
-        self.claims
-            .insert(Arc::from(reserved::NOT_BEFORE), not_before.as_ref().into());
-        self
+    pub fn with_expiration_time(self, expiration: DateTime<Utc>) -> Self {
+        self.with_string(reserved::EXPIRATION, &format_time(expiration))
     }
 
     #[must_use]
-    pub fn with_issued_at<T: AsRef<str>>(mut self, issued_at: T) -> Self {
-        self.claims
-            .insert(Arc::from(reserved::ISSUED_AT), issued_at.as_ref().into());
-        self
+    pub fn with_not_before_time(self, not_before: DateTime<Utc>) -> Self {
+        self.with_string(reserved::NOT_BEFORE, &format_time(not_before))
     }
 
     #[must_use]
-    pub fn with_token_identifier<T: AsRef<str>>(mut self, token_identifier: T) -> Self {
-        self.claims.insert(
-            Arc::from(reserved::TOKEN_IDENTIFIER),
-            token_identifier.as_ref().into(),
-        );
-        self
+    pub fn with_issued_at_time(self, issued_at: DateTime<Utc>) -> Self {
+        self.with_string(reserved::ISSUED_AT, &format_time(issued_at))
+    }
+
+    #[must_use]
+    pub fn expires_in(self, lifetime: Duration) -> Self {
+        self.with_expiration_time(Utc::now() + lifetime)
+    }
+
+    #[must_use]
+    pub fn valid_from_now(self) -> Self {
+        self.with_not_before_time(Utc::now())
+    }
+
+    #[must_use]
+    pub fn issued_now(self) -> Self {
+        self.with_issued_at_time(Utc::now())
     }
 
     /// # Errors
@@ -205,10 +282,23 @@ impl Claims {
     /// * `get_claim` - Retrieves a claim by key and attempts to deserialize it into the specified type.
     ///
     pub fn set_claim<T: Serialize>(&mut self, key: &str, value: T) -> Result<(), ClaimError> {
-        let value = serde_json::to_value(value)?;
+        self.insert(key, serde_json::to_value(value)?)
+    }
+
+    /// Sets a reserved claim whose value is valid by construction: a string for `iss`, `sub`, `aud` and `jti`,
+    /// or a formatted date for the time claims.
+    fn with_string(mut self, key: &str, value: &str) -> Self {
+        let value = Value::from(value);
+        debug_assert!(check_reserved(key, &value).is_ok());
+        self.claims.insert(Arc::from(key), value);
+        self
+    }
+
+    fn insert(&mut self, key: &str, value: Value) -> Result<(), ClaimError> {
         if value.is_null() {
             return Err(ClaimError::InvalidValue);
         }
+        check_reserved(key, &value)?;
         self.claims.insert(Arc::from(key), value);
         Ok(())
     }
@@ -220,6 +310,35 @@ impl Claims {
             .and_then(|value| serde_json::from_value(value.clone()).ok())
     }
 
+    /// Creates claims from a serializable value, typically a struct. Fields that serialize to `null`,
+    /// such as `None` options, are left out.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the value does not serialize to a JSON object or a reserved claim has the wrong type.
+    pub fn from_serializable<T: Serialize>(value: &T) -> Result<Self, ClaimError> {
+        let Value::Object(mut map) = serde_json::to_value(value)? else {
+            return Err(ClaimError::InvalidValue);
+        };
+        map.retain(|_, value| !value.is_null());
+        Self::try_from(Value::Object(map))
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if the claims cannot be deserialized into `T`.
+    pub fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, ClaimError> {
+        Ok(serde_json::from_value(serde_json::to_value(self)?)?)
+    }
+
+    pub fn remove_claim(&mut self, key: &str) -> Option<Value> {
+        self.claims.remove(key)
+    }
+
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.claims.contains_key(key)
+    }
+
     #[must_use]
     pub fn get_subject(&self) -> Option<String> {
         self.get_claim(reserved::SUBJECT)
@@ -256,6 +375,59 @@ impl Claims {
     }
 }
 
+/// Conversion between a user-defined type and [`Claims`].
+///
+/// Usually implemented with `#[derive(PasetoClaims)]` from the `derive` feature, which maps fields marked
+/// `#[paseto(exp)]`, `#[paseto(sub)]` and so on to the reserved claims and checks their types at compile time.
+/// The converted claims are passed to `Maker::create_token` and read back from `Maker::verify_token`.
+pub trait PasetoClaims: Sized {
+    /// # Errors
+    ///
+    /// This function will return an error if a field cannot be serialized into a valid claim.
+    fn to_claims(&self) -> Result<Claims, ClaimError>;
+
+    /// # Errors
+    ///
+    /// This function will return an error if a required claim is missing or has the wrong type.
+    fn from_claims(claims: &Claims) -> Result<Self, ClaimError>;
+}
+
+/// Reads and deserializes a claim, distinguishing a missing claim (`Ok(None)`) from one of the wrong type.
+#[doc(hidden)]
+pub fn claim<T: DeserializeOwned>(claims: &Claims, key: &str) -> Result<Option<T>, ClaimError> {
+    claims
+        .claims
+        .get(key)
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map_err(ClaimError::from)
+}
+
+/// Checks that a reserved claim has the type the PASETO specification requires. Other claims are accepted as is.
+fn check_reserved(key: &str, value: &Value) -> Result<(), ClaimError> {
+    let invalid =
+        |reason: &str| ClaimError::InvalidReservedClaim(key.to_string(), reason.to_string());
+    match key {
+        reserved::ISSUER | reserved::SUBJECT | reserved::AUDIENCE | reserved::TOKEN_IDENTIFIER => {
+            if value.is_string() {
+                Ok(())
+            } else {
+                Err(invalid("expected a string"))
+            }
+        }
+        reserved::EXPIRATION | reserved::NOT_BEFORE | reserved::ISSUED_AT => match value.as_str() {
+            Some(date) if DateTime::parse_from_rfc3339(date).is_ok() => Ok(()),
+            _ => Err(invalid("expected an RFC 3339 date")),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Formats a time claim as an RFC 3339 string with second precision, e.g. `2023-10-01T00:00:00+00:00`.
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
 #[cfg(test)]
 mod test {
     use serde_json::json;
@@ -284,7 +456,8 @@ mod test {
         let claims = Claims::new()
             .with_subject("1234567890")
             .with_audience("test audience")
-            .with_issued_at("2019-01-01T00:00:00+00:00");
+            .with_issued_at("2019-01-01T00:00:00+00:00")
+            .unwrap();
 
         let sub: Option<String> = claims.get_claim("sub");
         assert_eq!(sub, Some("1234567890".to_string()));
@@ -293,6 +466,146 @@ mod test {
 
         let sub: Option<String> = claims.get_claim("iat");
         assert_eq!(sub, Some("2019-01-01T00:00:00+00:00".to_string()));
+
+        assert!(matches!(
+            Claims::new().with_expiration("tomorrow"),
+            Err(ClaimError::InvalidReservedClaim(key, _)) if key == "exp"
+        ));
+        assert!(Claims::new().with_not_before("2019-01-01").is_err());
+        assert!(Claims::new().with_issued_at("").is_err());
+    }
+
+    #[test]
+    fn test_typed_time_setters() {
+        let time = DateTime::parse_from_rfc3339("2023-10-01T12:30:45.123+02:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let claims = Claims::new()
+            .with_expiration_time(time)
+            .with_not_before_time(time)
+            .with_issued_at_time(time);
+
+        let exp: Option<String> = claims.get_claim("exp");
+        assert_eq!(exp, Some("2023-10-01T10:30:45+00:00".to_string()));
+        let second = time - Duration::milliseconds(123);
+        assert_eq!(claims.get_expiration(), Some(second));
+        assert_eq!(claims.get_not_before(), Some(second));
+        assert_eq!(claims.get_issued_at(), Some(second));
+    }
+
+    #[test]
+    fn test_relative_time_setters() {
+        let before = Utc::now() - Duration::seconds(1);
+        let claims = Claims::new()
+            .expires_in(Duration::hours(1))
+            .valid_from_now()
+            .issued_now();
+        let after = Utc::now();
+
+        let exp = claims.get_expiration().unwrap();
+        assert!(exp >= before + Duration::hours(1) && exp <= after + Duration::hours(1));
+        let nbf = claims.get_not_before().unwrap();
+        assert!(nbf >= before && nbf <= after);
+        let iat = claims.get_issued_at().unwrap();
+        assert!(iat >= before && iat <= after);
+    }
+
+    #[test]
+    fn test_set_reserved_claim() {
+        let mut claims = Claims::new();
+        assert!(matches!(
+            claims.set_claim("exp", 5),
+            Err(ClaimError::InvalidReservedClaim(key, _)) if key == "exp"
+        ));
+        assert!(claims.set_claim("nbf", "tomorrow").is_err());
+        assert!(matches!(
+            claims.set_claim("iss", true),
+            Err(ClaimError::InvalidReservedClaim(key, _)) if key == "iss"
+        ));
+        assert!(claims.set_claim("aud", json!(["a", "b"])).is_err());
+        assert_eq!(claims.iter().count(), 0);
+
+        claims
+            .set_claim("iat", "2023-09-01T00:00:00+00:00")
+            .unwrap();
+        claims.set_claim("jti", "token_id").unwrap();
+        claims.set_claim("count", 5).unwrap();
+        assert_eq!(claims.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_try_from_value() {
+        let claims = Claims::try_from(json!({
+            "sub": "1234567890",
+            "exp": "2023-10-01T00:00:00+00:00",
+            "admin": true,
+        }))
+        .unwrap();
+        assert_eq!(claims.get_subject(), Some("1234567890".to_string()));
+        assert_eq!(claims.get_claim::<bool>("admin"), Some(true));
+
+        assert!(matches!(
+            Claims::try_from(json!({ "sub": 42 })),
+            Err(ClaimError::InvalidReservedClaim(key, _)) if key == "sub"
+        ));
+        assert!(Claims::try_from(json!({ "exp": "2023-10-01" })).is_err());
+        assert!(Claims::try_from(json!({ "name": null })).is_err());
+        assert!(Claims::try_from(json!(["sub"])).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Session {
+        #[serde(rename = "sub")]
+        user_id: String,
+        #[serde(rename = "exp")]
+        expires_at: DateTime<Utc>,
+        #[serde(rename = "jti", skip_serializing_if = "Option::is_none")]
+        token_id: Option<String>,
+        scopes: Vec<String>,
+    }
+
+    #[test]
+    fn test_serializable_round_trip() {
+        let session = Session {
+            user_id: "1234567890".to_string(),
+            expires_at: DateTime::parse_from_rfc3339("2023-10-01T00:00:00+00:00")
+                .unwrap()
+                .with_timezone(&Utc),
+            token_id: None,
+            scopes: vec!["read".to_string(), "write".to_string()],
+        };
+
+        let claims = Claims::from_serializable(&session).unwrap();
+        assert_eq!(claims.get_subject(), Some("1234567890".to_string()));
+        assert_eq!(claims.get_expiration(), Some(session.expires_at));
+        assert_eq!(claims.get_token_identifier(), None);
+        assert_eq!(claims.deserialize_into::<Session>().unwrap(), session);
+
+        assert!(Claims::from_serializable(&json!({ "sub": 1 })).is_err());
+        assert!(Claims::from_serializable(&"not an object").is_err());
+        assert!(Claims::from_serializable(&json!({ "jti": null }))
+            .unwrap()
+            .get_token_identifier()
+            .is_none());
+        assert!(claims.deserialize_into::<Vec<String>>().is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let claims = Claims::new()
+            .with_subject("1234567890")
+            .with_expiration("2023-10-01T00:00:00+00:00")
+            .unwrap();
+        let json = serde_json::to_string(&claims).unwrap();
+        assert_eq!(
+            json,
+            r#"{"exp":"2023-10-01T00:00:00+00:00","sub":"1234567890"}"#
+        );
+        assert_eq!(serde_json::from_str::<Claims>(&json).unwrap(), claims);
+        assert_eq!(claims.clone(), claims);
+
+        assert!(serde_json::from_str::<Claims>(r#"{"exp":5}"#).is_err());
+        assert!(serde_json::from_str::<Claims>("[]").is_err());
     }
 
     #[test]
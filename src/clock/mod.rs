@@ -0,0 +1,105 @@
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// A source of the current time for issuing and validating tokens.
+///
+/// `Maker`, `Verifier` and `KeyRing` read the [`SystemClock`] by default. Tests can inject a [`FixedClock`]
+/// to control time deterministically instead of sleeping.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// Reads the current time from the operating system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep a handle to advance the clock after passing it to a `Maker`.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, Clock, FixedClock, errors::TokenError};
+/// use chrono::{DateTime, Duration};
+///
+/// let clock = FixedClock::new(DateTime::from_timestamp(1_700_000_000, 0).unwrap());
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key)
+///     .expect("failed to create maker")
+///     .with_clock(clock.clone());
+///
+/// let token = maker.create_token(&Claims::new()).unwrap();
+/// let claims = maker.verify_token(&token).unwrap();
+/// assert_eq!(claims.get_issued_at(), Some(clock.now()));
+///
+/// clock.advance(Duration::hours(2));
+/// assert!(matches!(maker.verify_token(&token), Err(TokenError::Expired)));
+/// ```
+///
+/// # Methods
+///
+/// - `new`: Creates a clock frozen at the given time.
+/// - `advance`: Moves the clock forward, or backward for a negative duration.
+/// - `set`: Moves the clock to the given time.
+#[derive(Debug, Clone)]
+pub struct FixedClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl FixedClock {
+    #[must_use]
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) = now;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock() {
+        let start = Utc::now() - Duration::days(365);
+        let clock = FixedClock::new(start);
+        let handle = clock.clone();
+        assert_eq!(clock.now(), start);
+
+        handle.advance(Duration::minutes(5));
+        assert_eq!(clock.now(), start + Duration::minutes(5));
+
+        handle.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
@@ -1,9 +1,17 @@
+use rusty_paseto::{
+    core::PasetoError,
+    prelude::{GenericParserError, PasetoClaimError},
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ClaimError {
     #[error("invalid value")]
     InvalidValue,
+    #[error("invalid reserved claim {0}: {1}")]
+    InvalidReservedClaim(String, String),
+    #[error("missing claim: {0}")]
+    MissingClaim(String),
     #[error("serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
     #[error("paseto error: {0}")]
@@ -12,27 +20,93 @@ pub enum ClaimError {
     InvalidClaim(#[from] rusty_paseto::generic::PasetoClaimError),
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Error)]
 pub enum TokenError {
     #[error("Invalid claim: {0}")]
     InvalidClaim(String),
     #[error("Token expired")]
     Expired,
+    #[error("Token not yet valid")]
+    NotYetValid,
+    #[error("Token issued in the future")]
+    IssuedInFuture,
     #[error("Token not valid")]
-    Invalid,
-    #[error("Token validation failed")]
-    Validation,
+    Invalid(#[source] GenericParserError),
+    #[error("Token header does not match the expected version and purpose")]
+    WrongHeader(#[source] GenericParserError),
+    #[error("Token validation failed: {0}")]
+    Validation(#[from] ValidationError),
     #[error("Token malformed")]
-    Format,
+    Format(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Token footer rejected")]
+    FooterMismatch,
+    #[error("Token was created with a different key")]
+    KeyIdMismatch,
+    #[error("Token key id '{0}' is unknown")]
+    UnknownKeyId(String),
+    #[error("No key available to verify the token")]
+    NoMatchingKey,
+    #[error("Token revoked")]
+    Revoked,
+    #[error("Token already used")]
+    Replayed,
+    #[error("Refresh token reused, its token family was revoked")]
+    RefreshTokenReused,
+    #[error("Store error: {0}")]
+    Store(#[from] StoreError),
     #[error("Claim error: {0}")]
     ClaimError(#[from] ClaimError),
     #[error("Token creation failed: {0}")]
     TokenCreationFailed(String),
 }
 
+impl From<GenericParserError> for TokenError {
+    fn from(err: GenericParserError) -> Self {
+        match &err {
+            GenericParserError::CipherError { source } => match source {
+                PasetoError::WrongHeader => Self::WrongHeader(err),
+                PasetoError::IncorrectSize
+                | PasetoError::FooterInvalid
+                | PasetoError::PayloadBase64Decode { .. }
+                | PasetoError::TryFromSlice { .. }
+                | PasetoError::Utf8Error { .. }
+                | PasetoError::FromUtf8Error { .. } => Self::Format(Box::new(err)),
+                _ => Self::Invalid(err),
+            },
+            GenericParserError::ClaimError { source } => match source {
+                PasetoClaimError::Expired => Self::Expired,
+                PasetoClaimError::UseBeforeAvailable(_) => Self::NotYetValid,
+                _ => Self::InvalidClaim(err.to_string()),
+            },
+            GenericParserError::PayloadJsonError { .. } => Self::Format(Box::new(err)),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ValidationError {
+    #[error("missing required claim '{0}'")]
+    MissingClaim(String),
+    #[error("unexpected value for claim '{0}': {1}")]
+    UnexpectedValue(String, String),
+    #[error("token exceeds the maximum age")]
+    TooOld,
+}
+
 #[derive(Error, Debug)]
 pub enum MakerError {
     #[error("Invalid key: {0}")]
     InvalidKey(String),
+    #[error("Unknown key: {0}")]
+    UnknownKey(String),
+    #[error("Duplicate key: {0}")]
+    DuplicateKey(String),
+}
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("corrupt store entry: {0}")]
+    Corrupt(String),
 }
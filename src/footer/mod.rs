@@ -0,0 +1,193 @@
+use crate::errors::{ClaimError, TokenError};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::fmt::{self, Display, Formatter};
+
+/// Represents the unencrypted footer of a PASETO token.
+///
+/// A footer is either a raw string or a JSON object. Footers are authenticated together with the
+/// token but never encrypted, so they must not contain secrets.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::Footer;
+/// use serde_json::json;
+///
+/// let raw = Footer::raw("some metadata");
+/// assert_eq!(raw.to_string(), "some metadata");
+///
+/// let footer = Footer::json(json!({ "kid": "key-1" })).unwrap();
+/// let kid: Option<String> = footer.get_claim("kid");
+/// assert_eq!(kid, Some("key-1".to_string()));
+/// assert_eq!(footer.to_string(), r#"{"kid":"key-1"}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Footer {
+    Raw(String),
+    Json(Map<String, Value>),
+}
+
+impl Display for Footer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Raw(footer) => write!(f, "{footer}"),
+            Self::Json(map) => write!(f, "{}", Value::Object(map.clone())),
+        }
+    }
+}
+
+impl From<&str> for Footer {
+    fn from(footer: &str) -> Self {
+        Self::raw(footer)
+    }
+}
+
+impl From<String> for Footer {
+    fn from(footer: String) -> Self {
+        Self::Raw(footer)
+    }
+}
+
+impl Footer {
+    #[must_use]
+    pub fn raw<T: AsRef<str>>(footer: T) -> Self {
+        Self::Raw(footer.as_ref().to_string())
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if the value cannot be serialized or is not a JSON object.
+    pub fn json<T: Serialize>(value: T) -> Result<Self, ClaimError> {
+        match serde_json::to_value(value)? {
+            Value::Object(map) => Ok(Self::Json(map)),
+            _ => Err(ClaimError::InvalidValue),
+        }
+    }
+
+    /// Parses a decoded footer, treating it as JSON if it is a JSON object and as a raw string otherwise.
+    #[must_use]
+    pub fn parse(footer: &str) -> Self {
+        match serde_json::from_str(footer) {
+            Ok(Value::Object(map)) => Self::Json(map),
+            _ => Self::raw(footer),
+        }
+    }
+
+    /// Retrieves a value of a JSON footer by key. Always returns `None` for raw footers.
+    #[must_use]
+    pub fn get_claim<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        match self {
+            Self::Json(map) => map
+                .get(key)
+                .and_then(|value| serde_json::from_value(value.clone()).ok()),
+            Self::Raw(_) => None,
+        }
+    }
+}
+
+/// The footer field that names the key a token was created with.
+pub(crate) const KEY_ID: &str = "kid";
+
+/// Returns `footer` with its `kid` field set to `key_id`, creating a JSON footer if there is none.
+pub(crate) fn with_key_id(footer: Option<&Footer>, key_id: &str) -> Result<Footer, TokenError> {
+    let mut map = match footer {
+        Some(Footer::Json(map)) => map.clone(),
+        Some(Footer::Raw(_)) => {
+            return Err(TokenError::TokenCreationFailed(
+                "a key id cannot be embedded into a raw footer".to_string(),
+            ))
+        }
+        None => Map::new(),
+    };
+    map.insert(KEY_ID.to_string(), key_id.into());
+    Ok(Footer::Json(map))
+}
+
+/// Rejects a token whose footer `kid` is a PASERK ID of the same type as `key_id` but names another key.
+///
+/// Footers without a `kid`, or with an application-defined `kid`, are accepted.
+pub(crate) fn check_key_id(footer: Option<&str>, key_id: &str) -> Result<(), TokenError> {
+    let Some(Footer::Json(map)) = footer.map(Footer::parse) else {
+        return Ok(());
+    };
+    let header = key_id.rsplit_once('.').map_or(key_id, |(header, _)| header);
+    match map.get(KEY_ID).and_then(Value::as_str) {
+        Some(kid) if kid.starts_with(header) && kid != key_id => Err(TokenError::KeyIdMismatch),
+        _ => Ok(()),
+    }
+}
+
+/// Extracts and decodes the footer segment of a token without verifying it.
+pub(crate) fn decode_footer(token: &str) -> Result<Option<String>, TokenError> {
+    match token.split('.').nth(3) {
+        Some(encoded) => {
+            let decoded = URL_SAFE_NO_PAD
+                .decode(encoded)
+                .map_err(|err| TokenError::Format(Box::new(err)))?;
+            String::from_utf8(decoded)
+                .map(Some)
+                .map_err(|err| TokenError::Format(Box::new(err)))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_footer() {
+        assert_eq!(Footer::parse("plain"), Footer::raw("plain"));
+        assert_eq!(Footer::parse("[1, 2]"), Footer::raw("[1, 2]"));
+        assert_eq!(
+            Footer::parse(r#"{"kid":"key-1"}"#),
+            Footer::json(json!({ "kid": "key-1" })).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_footer_must_be_object() {
+        assert!(Footer::json("not an object").is_err());
+        assert!(Footer::json(json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_key_id() {
+        let footer = with_key_id(None, "k4.pid.abc").unwrap();
+        assert_eq!(footer.get_claim::<String>(KEY_ID).unwrap(), "k4.pid.abc");
+
+        let existing = Footer::json(json!({ "app": "test" })).unwrap();
+        let footer = with_key_id(Some(&existing), "k4.pid.abc").unwrap();
+        assert_eq!(footer.get_claim::<String>("app").unwrap(), "test");
+        assert_eq!(footer.get_claim::<String>(KEY_ID).unwrap(), "k4.pid.abc");
+
+        assert!(with_key_id(Some(&Footer::raw("raw")), "k4.pid.abc").is_err());
+
+        let encoded = footer.to_string();
+        assert!(check_key_id(Some(&encoded), "k4.pid.abc").is_ok());
+        assert!(matches!(
+            check_key_id(Some(&encoded), "k4.pid.other"),
+            Err(TokenError::KeyIdMismatch)
+        ));
+        assert!(check_key_id(Some(r#"{"kid":"key-1"}"#), "k4.pid.other").is_ok());
+        assert!(check_key_id(Some(r#"{"kid":"k4.lid.abc"}"#), "k4.pid.other").is_ok());
+        assert!(check_key_id(Some("raw"), "k4.pid.other").is_ok());
+        assert!(check_key_id(None, "k4.pid.other").is_ok());
+    }
+
+    #[test]
+    fn test_decode_footer() {
+        assert_eq!(decode_footer("v4.public.payload").unwrap(), None);
+        assert_eq!(
+            decode_footer("v4.public.payload.Zm9vdGVy").unwrap(),
+            Some("footer".to_string())
+        );
+        assert!(decode_footer("v4.public.payload.!!").is_err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Conversion of V4 keys to and from JSON Web Keys ([RFC 7517](https://www.rfc-editor.org/rfc/rfc7517)).
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::MakerError,
+    purpose::{Local, Public},
+    version::V4,
+    KeyRing, Maker, Verifier,
+};
+
+const OKP: &str = "OKP";
+const OCT: &str = "oct";
+const ED25519: &str = "Ed25519";
+const EDDSA: &str = "EdDSA";
+const SIGNATURE: &str = "sig";
+
+/// A JSON Web Key holding either an Ed25519 public key (`"kty": "OKP"`) or a symmetric `V4.local` key
+/// (`"kty": "oct"`).
+///
+/// Keys are exported with their PASERK ID as `kid`, the same id a `Maker` embeds in token footers with
+/// `with_key_id_footer` and a [`KeyRing`] assigns by default. Private Ed25519 keys are never exported.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, Verifier};
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker");
+/// let jwk = maker.to_jwk_public();
+/// assert_eq!(jwk.kid, Some(maker.key_id()));
+///
+/// let json = serde_json::to_string(&jwk).unwrap();
+/// let verifier = Verifier::from_jwk(&serde_json::from_str(&json).unwrap()).unwrap();
+/// let token = maker.create_token(&Claims::new()).unwrap();
+/// assert!(verifier.verify_token(&token).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// The base64url encoded public key of an `OKP` key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// The base64url encoded symmetric key of an `oct` key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    pub key_use: Option<String>,
+}
+
+impl Jwk {
+    /// Returns an `OKP` JWK for an Ed25519 public key.
+    pub(crate) fn ed25519(public_key: &[u8; 32], kid: &str) -> Self {
+        Self {
+            kty: OKP.to_string(),
+            crv: Some(ED25519.to_string()),
+            x: Some(URL_SAFE_NO_PAD.encode(public_key)),
+            k: None,
+            kid: Some(kid.to_string()),
+            alg: Some(EDDSA.to_string()),
+            key_use: Some(SIGNATURE.to_string()),
+        }
+    }
+
+    /// Sets the `kid` of the key, e.g. to match an application-defined key id.
+    #[must_use]
+    pub fn with_kid<T: AsRef<str>>(mut self, kid: T) -> Self {
+        self.kid = Some(kid.as_ref().to_string());
+        self
+    }
+
+    fn expect_type(&self, kty: &str) -> Result<(), MakerError> {
+        if self.kty == kty {
+            Ok(())
+        } else {
+            Err(MakerError::InvalidKey(format!(
+                "expected a JWK with kty '{kty}', got '{}'",
+                self.kty
+            )))
+        }
+    }
+}
+
+/// Decodes the base64url encoded 32-byte key in the JWK member `name`.
+fn decode_member(name: &str, value: Option<&str>) -> Result<[u8; 32], MakerError> {
+    let value = value
+        .ok_or_else(|| MakerError::InvalidKey(format!("JWK is missing the '{name}' member")))?;
+    let key = URL_SAFE_NO_PAD.decode(value).map_err(|err| {
+        MakerError::InvalidKey(format!("invalid base64url in JWK member '{name}': {err}"))
+    })?;
+    let len = key.len();
+    key.try_into().map_err(|_| {
+        MakerError::InvalidKey(format!(
+            "expected a 32 byte key in JWK member '{name}', got {len} bytes"
+        ))
+    })
+}
+
+impl Maker<V4, Public> {
+    /// Returns the public key as an `OKP`/`Ed25519` JWK with the `k4.pid` PASERK ID as `kid`.
+    #[must_use]
+    pub fn to_jwk_public(&self) -> Jwk {
+        Jwk::ed25519(self.public_key_as_bytes(), &self.key_id())
+    }
+}
+
+impl Verifier<V4, Public> {
+    /// Creates a new `Verifier` from an `OKP`/`Ed25519` JWK.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the JWK is not an `OKP` key on the `Ed25519` curve or its `x`
+    /// member is not a valid Ed25519 public key.
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, MakerError> {
+        jwk.expect_type(OKP)?;
+        if jwk.crv.as_deref() != Some(ED25519) {
+            return Err(MakerError::InvalidKey(format!(
+                "expected a JWK with crv '{ED25519}', got '{}'",
+                jwk.crv.as_deref().unwrap_or_default()
+            )));
+        }
+        Self::new(&decode_member("x", jwk.x.as_deref())?)
+    }
+
+    /// Returns the public key as an `OKP`/`Ed25519` JWK with the `k4.pid` PASERK ID as `kid`.
+    #[must_use]
+    pub fn to_jwk_public(&self) -> Jwk {
+        Jwk::ed25519(self.public_key_as_bytes(), &self.key_id())
+    }
+}
+
+impl Maker<V4, Local> {
+    /// Creates a new `Maker` from an `oct` JWK holding a 32-byte symmetric key.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the JWK is not an `oct` key or its `k` member is not a 32-byte key.
+    pub fn from_jwk_local(jwk: &Jwk) -> Result<Self, MakerError> {
+        jwk.expect_type(OCT)?;
+        Ok(Self::new_local(&decode_member("k", jwk.k.as_deref())?))
+    }
+
+    /// Returns the symmetric key as an `oct` JWK with the `k4.lid` PASERK ID as `kid`. The JWK contains the
+    /// secret key and must be kept as confidential as the key itself.
+    #[must_use]
+    pub fn to_jwk_local(&self) -> Jwk {
+        Jwk {
+            kty: OCT.to_string(),
+            crv: None,
+            x: None,
+            k: Some(URL_SAFE_NO_PAD.encode(self.key_as_bytes())),
+            kid: Some(self.key_id()),
+            alg: None,
+            key_use: None,
+        }
+    }
+}
+
+/// A JWK Set document, `{"keys": [...]}`, for publishing the public keys that verify tokens.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, JwkSet};
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker").with_key_id_footer();
+/// let document = serde_json::to_string(&JwkSet::new().with_key(maker.to_jwk_public())).unwrap();
+///
+/// let keys: JwkSet = serde_json::from_str(&document).unwrap();
+/// let ring = keys.to_key_ring().unwrap();
+/// let token = maker.create_token(&Claims::new()).unwrap();
+/// assert!(ring.verify_token(&token).is_ok());
+/// ```
+///
+/// # Methods
+///
+/// - `new`: Creates an empty `JwkSet`.
+/// - `with_key`: Adds a key to the set.
+/// - `find`: Returns the key with the given `kid`.
+/// - `verifier`: Builds a [`Verifier`] for the key with the given `kid`.
+/// - `to_key_ring`: Builds a [`KeyRing`] of verifying keys from every `OKP`/`Ed25519` key in the set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+impl JwkSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_key(mut self, jwk: Jwk) -> Self {
+        self.keys.push(jwk);
+        self
+    }
+
+    #[must_use]
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys.iter().find(|jwk| jwk.kid.as_deref() == Some(kid))
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if there is no key with the given `kid` or it is not a valid
+    /// `OKP`/`Ed25519` key.
+    pub fn verifier(&self, kid: &str) -> Result<Verifier<V4, Public>, MakerError> {
+        let jwk = self
+            .find(kid)
+            .ok_or_else(|| MakerError::UnknownKey(kid.to_string()))?;
+        Verifier::from_jwk(jwk)
+    }
+
+    /// Builds a [`KeyRing`] that verifies tokens with every `OKP`/`Ed25519` key in the set. Keys without a
+    /// `kid` are added under their `k4.pid` PASERK ID. Keys of other types, such as RSA keys published for
+    /// other services, are skipped.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if an `OKP`/`Ed25519` key is invalid or two keys share a `kid`.
+    pub fn to_key_ring(&self) -> Result<KeyRing, MakerError> {
+        let mut ring = KeyRing::new();
+        for jwk in &self.keys {
+            if jwk.kty != OKP || jwk.crv.as_deref() != Some(ED25519) {
+                continue;
+            }
+            let verifier = Verifier::from_jwk(jwk)?;
+            match &jwk.kid {
+                Some(kid) => ring.add_verifying_key_with_id(kid, verifier)?,
+                None => {
+                    ring.add_verifying_key(verifier)?;
+                }
+            }
+        }
+        Ok(ring)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Claims, Footer};
+
+    // Test vector from RFC 8037, appendix A.2.
+    const RFC_PUBLIC_KEY: &str = "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo";
+
+    #[test]
+    fn test_okp_round_trip() {
+        let jwk: Jwk = serde_json::from_value(json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "x": RFC_PUBLIC_KEY,
+        }))
+        .unwrap();
+        let verifier = Verifier::from_jwk(&jwk).unwrap();
+
+        let exported = serde_json::to_value(verifier.to_jwk_public()).unwrap();
+        assert_eq!(
+            exported,
+            json!({
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": RFC_PUBLIC_KEY,
+                "kid": verifier.key_id(),
+                "alg": "EdDSA",
+                "use": "sig",
+            })
+        );
+    }
+
+    #[test]
+    fn test_oct_round_trip() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let jwk = maker.to_jwk_local();
+        assert_eq!(jwk.kty, "oct");
+        assert_eq!(jwk.kid, Some(maker.key_id()));
+
+        let imported = Maker::from_jwk_local(&jwk).unwrap();
+        assert_eq!(imported.key_as_bytes(), maker.key_as_bytes());
+        let token = maker.create_token(&Claims::new()).unwrap();
+        assert!(imported.verify_token(&token).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_jwks() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let oct = maker.to_jwk_local();
+        assert!(matches!(
+            Verifier::from_jwk(&oct),
+            Err(MakerError::InvalidKey(reason)) if reason == "expected a JWK with kty 'OKP', got 'oct'"
+        ));
+
+        let (priv_key, _) = Maker::new_keypair();
+        let okp = Maker::new(&priv_key).unwrap().to_jwk_public();
+        assert!(Maker::from_jwk_local(&okp).is_err());
+
+        let x25519 = Jwk {
+            crv: Some("X25519".to_string()),
+            ..okp.clone()
+        };
+        assert!(matches!(
+            Verifier::from_jwk(&x25519),
+            Err(MakerError::InvalidKey(reason)) if reason == "expected a JWK with crv 'Ed25519', got 'X25519'"
+        ));
+
+        let short = Jwk {
+            k: Some("AAAA".to_string()),
+            ..oct
+        };
+        assert!(matches!(
+            Maker::from_jwk_local(&short),
+            Err(MakerError::InvalidKey(reason)) if reason == "expected a 32 byte key in JWK member 'k', got 3 bytes"
+        ));
+
+        let missing = Jwk { x: None, ..okp };
+        assert!(matches!(
+            Verifier::from_jwk(&missing),
+            Err(MakerError::InvalidKey(reason)) if reason == "JWK is missing the 'x' member"
+        ));
+    }
+
+    #[test]
+    fn test_key_set() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).unwrap();
+        let custom = Maker::new(&Maker::new_keypair().0).unwrap();
+
+        let set = JwkSet::new()
+            .with_key(maker.to_jwk_public())
+            .with_key(custom.to_jwk_public().with_kid("custom"))
+            .with_key(Maker::new_local(&Maker::new_local_key()).to_jwk_local());
+        let set: JwkSet = serde_json::from_str(&serde_json::to_string(&set).unwrap()).unwrap();
+
+        let token = maker.create_token(&Claims::new()).unwrap();
+        assert!(set
+            .verifier(&maker.key_id())
+            .unwrap()
+            .verify_token(&token)
+            .is_ok());
+        assert!(matches!(
+            set.verifier("unknown"),
+            Err(MakerError::UnknownKey(_))
+        ));
+
+        let ring = set.to_key_ring().unwrap();
+        assert!(ring.verify_token(&token).is_ok());
+        let footer = Footer::json(json!({ "kid": "custom" })).unwrap();
+        let custom_token = Maker::new(&priv_key)
+            .unwrap()
+            .create_token_with_footer(&Claims::new(), &footer)
+            .unwrap();
+        assert!(ring.verify_token(&custom_token).is_err());
+
+        let published = ring.to_jwk_set();
+        assert_eq!(published.keys.len(), 2);
+        assert!(published.find("custom").is_some());
+
+        let duplicate = set.clone().with_key(maker.to_jwk_public());
+        assert!(matches!(
+            duplicate.to_key_ring(),
+            Err(MakerError::DuplicateKey(_))
+        ));
+    }
+}
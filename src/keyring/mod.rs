@@ -0,0 +1,488 @@
+use crate::{
+    clock::{Clock, SystemClock},
+    errors::{MakerError, TokenError},
+    footer::{decode_footer, with_key_id, Footer, KEY_ID},
+    jwk::{Jwk, JwkSet},
+    maker::parse_token,
+    purpose::Public,
+    token::{SignedToken, TokenInput},
+    validation::Validation,
+    version::V4,
+    Claims, Maker, Verifier,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+
+enum KeyRingKey {
+    Signing(Maker<V4, Public>),
+    Verifying(Verifier<V4, Public>),
+}
+
+impl KeyRingKey {
+    fn public_key_as_bytes(&self) -> &[u8; 32] {
+        match self {
+            Self::Signing(maker) => maker.public_key_as_bytes(),
+            Self::Verifying(verifier) => verifier.public_key_as_bytes(),
+        }
+    }
+}
+
+struct Entry {
+    id: String,
+    key: KeyRingKey,
+    retire_at: Option<DateTime<Utc>>,
+}
+
+impl Entry {
+    fn is_retired(&self, now: DateTime<Utc>) -> bool {
+        self.retire_at.is_some_and(|retire_at| now >= retire_at)
+    }
+}
+
+/// `KeyRing` holds several `V4.public` keys to support key rotation.
+///
+/// Tokens are signed with the active key and carry its id as `{"kid": ...}` in the footer. Verification picks
+/// the key named by the footer `kid`, or tries every key if the token has none. Keys added without an explicit
+/// id are tagged with their `k4.pid` PASERK ID.
+///
+/// # Methods
+///
+/// - `new() -> Self`
+///   - Creates an empty `KeyRing`.
+/// - `add_signing_key(&mut self, maker: Maker<V4, Public>) -> Result<String, MakerError>`
+///   - Adds a key that can sign and verify tokens and returns its id.
+/// - `add_verifying_key(&mut self, verifier: Verifier<V4, Public>) -> Result<String, MakerError>`
+///   - Adds a key that can only verify tokens and returns its id.
+/// - `set_active(&mut self, id: &str) -> Result<(), MakerError>`
+///   - Marks a signing key as the one used by `create_token`.
+/// - `retire(&mut self, id: &str, grace_period: Duration) -> Result<(), MakerError>`
+///   - Stops signing with a key and stops accepting its tokens once the grace period has passed.
+/// - `revoke(&mut self, id: &str) -> Result<(), MakerError>`
+///   - Stops signing with a key and accepting its tokens immediately.
+/// - `to_jwk_set(&self) -> JwkSet`
+///   - Returns the public keys that are not retired as a JWK Set document.
+/// - `create_token(&self, claims: &Claims) -> Result<SignedToken<V4, Public>, TokenError>`
+///   - Creates a new PASETO token with the active key.
+/// - `verify_token(&self, token: impl TokenInput<V4, Public>) -> Result<Claims, TokenError>`
+///   - Verifies a PASETO token against the key ring.
+///
+/// # Example
+///
+/// ```rust
+/// use paseto_maker::{Maker, Claims, KeyRing};
+/// use chrono::Duration;
+///
+/// let mut ring = KeyRing::new();
+/// let old = ring.add_signing_key(Maker::new(&Maker::new_keypair().0).unwrap()).unwrap();
+/// ring.set_active(&old).unwrap();
+/// let token = ring.create_token(&Claims::new()).unwrap();
+///
+/// let new = ring.add_signing_key(Maker::new(&Maker::new_keypair().0).unwrap()).unwrap();
+/// ring.set_active(&new).unwrap();
+/// ring.retire(&old, Duration::hours(1)).unwrap();
+///
+/// assert!(ring.verify_token(&token).is_ok());
+/// ```
+pub struct KeyRing {
+    entries: Vec<Entry>,
+    active: Option<String>,
+    validation: Validation,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for KeyRing {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            active: None,
+            validation: Validation::new(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+}
+
+impl KeyRing {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Validation`] policy applied by `verify_token`.
+    #[must_use]
+    pub fn with_validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Sets the [`Clock`] used for grace periods and for validating verified tokens. Signing keys keep their own clock
+    /// for the time claims of created tokens.
+    #[must_use]
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if a key with the same id is already in the ring.
+    pub fn add_signing_key(&mut self, maker: Maker<V4, Public>) -> Result<String, MakerError> {
+        let id = maker.key_id();
+        self.add_signing_key_with_id(&id, maker)?;
+        Ok(id)
+    }
+
+    /// Adds a key that can sign and verify tokens under `id`. The ring sets the footer `kid` of its tokens itself,
+    /// so [`Maker::with_key_id_footer`] has no effect on makers in a ring.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a key with the same id is already in the ring.
+    pub fn add_signing_key_with_id(
+        &mut self,
+        id: &str,
+        maker: Maker<V4, Public>,
+    ) -> Result<(), MakerError> {
+        self.insert(id, KeyRingKey::Signing(maker.without_key_id_footer()))
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if a key with the same id is already in the ring.
+    pub fn add_verifying_key(
+        &mut self,
+        verifier: Verifier<V4, Public>,
+    ) -> Result<String, MakerError> {
+        let id = verifier.key_id();
+        self.add_verifying_key_with_id(&id, verifier)?;
+        Ok(id)
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if a key with the same id is already in the ring.
+    pub fn add_verifying_key_with_id(
+        &mut self,
+        id: &str,
+        verifier: Verifier<V4, Public>,
+    ) -> Result<(), MakerError> {
+        self.insert(id, KeyRingKey::Verifying(verifier))
+    }
+
+    fn insert(&mut self, id: &str, key: KeyRingKey) -> Result<(), MakerError> {
+        if self.entries.iter().any(|entry| entry.id == id) {
+            return Err(MakerError::DuplicateKey(id.to_string()));
+        }
+        self.entries.push(Entry {
+            id: id.to_string(),
+            key,
+            retire_at: None,
+        });
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if the key is unknown, retired or cannot sign tokens.
+    pub fn set_active(&mut self, id: &str) -> Result<(), MakerError> {
+        match self.entries.iter().find(|entry| entry.id == id) {
+            Some(entry) if entry.retire_at.is_some() => {
+                Err(MakerError::InvalidKey(format!("key '{id}' is retired")))
+            }
+            Some(Entry {
+                key: KeyRingKey::Signing(_),
+                ..
+            }) => {
+                self.active = Some(id.to_string());
+                Ok(())
+            }
+            Some(_) => Err(MakerError::InvalidKey(format!(
+                "key '{id}' cannot sign tokens"
+            ))),
+            None => Err(MakerError::UnknownKey(id.to_string())),
+        }
+    }
+
+    #[must_use]
+    pub fn active_key_id(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    /// Stops signing with the key immediately and stops accepting its tokens once `grace_period` has passed.
+    ///
+    /// Retiring a key again can only shorten its grace period, never extend it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the key is unknown.
+    pub fn retire(&mut self, id: &str, grace_period: Duration) -> Result<(), MakerError> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| MakerError::UnknownKey(id.to_string()))?;
+        let retire_at = self.clock.now() + grace_period;
+        entry.retire_at = Some(
+            entry
+                .retire_at
+                .map_or(retire_at, |earlier| earlier.min(retire_at)),
+        );
+        if self.active.as_deref() == Some(id) {
+            self.active = None;
+        }
+        Ok(())
+    }
+
+    /// Stops signing with the key and accepting its tokens immediately, e.g. once it has been compromised.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the key is unknown.
+    pub fn revoke(&mut self, id: &str) -> Result<(), MakerError> {
+        self.retire(id, Duration::zero())
+    }
+
+    /// Returns the public keys that are not retired as a [`JwkSet`], each with its id in the ring as `kid`.
+    #[must_use]
+    pub fn to_jwk_set(&self) -> JwkSet {
+        let now = self.clock.now();
+        self.entries
+            .iter()
+            .filter(|entry| !entry.is_retired(now))
+            .fold(JwkSet::new(), |set, entry| {
+                set.with_key(Jwk::ed25519(entry.key.public_key_as_bytes(), &entry.id))
+            })
+    }
+
+    /// Removes all keys whose grace period has passed.
+    pub fn prune_retired(&mut self) {
+        let now = self.clock.now();
+        self.entries.retain(|entry| !entry.is_retired(now));
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if there is no active key or the token creation fails.
+    pub fn create_token(&self, claims: &Claims) -> Result<SignedToken<V4, Public>, TokenError> {
+        self.build_token(claims, None)
+    }
+
+    /// Creates a new PASETO token with the active key, adding its `kid` to the given JSON footer.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is no active key, the footer is a raw footer or the token
+    /// creation fails.
+    pub fn create_token_with_footer(
+        &self,
+        claims: &Claims,
+        footer: &Footer,
+    ) -> Result<SignedToken<V4, Public>, TokenError> {
+        self.build_token(claims, Some(footer))
+    }
+
+    fn build_token(
+        &self,
+        claims: &Claims,
+        footer: Option<&Footer>,
+    ) -> Result<SignedToken<V4, Public>, TokenError> {
+        let (id, maker) = self
+            .active
+            .as_deref()
+            .and_then(|id| {
+                self.entries.iter().find_map(|entry| match &entry.key {
+                    KeyRingKey::Signing(maker) if entry.id == id => Some((id, maker)),
+                    _ => None,
+                })
+            })
+            .ok_or_else(|| TokenError::TokenCreationFailed("no active signing key".to_string()))?;
+        maker.create_token_with_footer(claims, &with_key_id(footer, id)?)
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if no key in the ring verifies the token.
+    pub fn verify_token(&self, token: impl TokenInput<V4, Public>) -> Result<Claims, TokenError> {
+        self.verify_token_with_footer(token)
+            .map(|(claims, _)| claims)
+    }
+
+    /// Verifies a PASETO token against the key ring and returns its claims together with its footer, if any.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no key in the ring verifies the token.
+    pub fn verify_token_with_footer(
+        &self,
+        token: impl TokenInput<V4, Public>,
+    ) -> Result<(Claims, Option<Footer>), TokenError> {
+        let token = token.as_token();
+        let now = self.clock.now();
+        let kid = decode_footer(token)?
+            .map(|footer| Footer::parse(&footer))
+            .and_then(|footer| footer.get_claim::<String>(KEY_ID));
+
+        if let Some(kid) = kid {
+            let entry = self
+                .entries
+                .iter()
+                .find(|entry| entry.id == kid && !entry.is_retired(now))
+                .ok_or(TokenError::UnknownKeyId(kid))?;
+            return parse_token::<Public>(
+                entry.key.public_key_as_bytes(),
+                token,
+                None,
+                &self.validation,
+                now,
+            );
+        }
+
+        let mut result = Err(TokenError::NoMatchingKey);
+        for entry in self.entries.iter().filter(|entry| !entry.is_retired(now)) {
+            result = parse_token::<Public>(
+                entry.key.public_key_as_bytes(),
+                token,
+                None,
+                &self.validation,
+                now,
+            );
+            if !matches!(result, Err(TokenError::Invalid(_))) {
+                return result;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_maker() -> Maker<V4, Public> {
+        Maker::new(&Maker::new_keypair().0).expect("failed to create maker")
+    }
+
+    #[test]
+    fn test_rotation() {
+        let mut ring = KeyRing::new();
+        let old = ring.add_signing_key(new_maker()).unwrap();
+        ring.set_active(&old).unwrap();
+        let old_token = ring
+            .create_token(&Claims::new().with_subject("old"))
+            .expect("failed to generate token");
+
+        let new = ring.add_signing_key(new_maker()).unwrap();
+        ring.set_active(&new).unwrap();
+        assert_eq!(ring.active_key_id(), Some(new.as_str()));
+        let new_token = ring
+            .create_token(&Claims::new().with_subject("new"))
+            .expect("failed to generate token");
+
+        let (claims, footer) = ring
+            .verify_token_with_footer(&old_token)
+            .expect("failed to verify token");
+        assert_eq!(claims.get_subject().unwrap(), "old");
+        assert_eq!(
+            footer.unwrap().get_claim::<String>("kid"),
+            Some(old.clone())
+        );
+        assert_eq!(
+            ring.verify_token(&new_token)
+                .unwrap()
+                .get_subject()
+                .unwrap(),
+            "new"
+        );
+
+        ring.retire(&old, Duration::hours(1)).unwrap();
+        assert!(ring.verify_token(&old_token).is_ok());
+
+        ring.revoke(&old).unwrap();
+        assert!(matches!(
+            ring.verify_token(&old_token),
+            Err(TokenError::UnknownKeyId(_))
+        ));
+        assert!(ring.set_active(&old).is_err());
+        assert!(ring.verify_token(&new_token).is_ok());
+
+        ring.prune_retired();
+        assert!(matches!(
+            ring.set_active(&old),
+            Err(MakerError::UnknownKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_grace_period_with_clock() {
+        let clock = crate::FixedClock::new(Utc::now());
+        let mut ring = KeyRing::new().with_clock(clock.clone());
+        let old = ring
+            .add_signing_key(new_maker().with_clock(clock.clone()))
+            .unwrap();
+        ring.set_active(&old).unwrap();
+        let token = ring
+            .create_token(&Claims::new().expires_in(Duration::days(1)))
+            .expect("failed to generate token");
+
+        ring.retire(&old, Duration::minutes(30)).unwrap();
+        ring.retire(&old, Duration::hours(2)).unwrap();
+        clock.advance(Duration::minutes(29));
+        assert!(ring.verify_token(&token).is_ok());
+        clock.advance(Duration::minutes(1));
+        assert!(matches!(
+            ring.verify_token(&token),
+            Err(TokenError::UnknownKeyId(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom_ids_and_verifying_keys() {
+        let maker = new_maker().with_key_id_footer();
+        let verifier = maker.verifier();
+        let mut ring = KeyRing::new();
+        ring.add_signing_key_with_id("2024-01", maker).unwrap();
+        ring.set_active("2024-01").unwrap();
+        let token = ring
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        assert_eq!(
+            token.footer().unwrap().get_claim::<String>("kid"),
+            Some("2024-01".to_string())
+        );
+        assert!(ring.verify_token(&token).is_ok());
+
+        let mut verifying_ring = KeyRing::new();
+        verifying_ring
+            .add_verifying_key_with_id("2024-01", verifier)
+            .unwrap();
+        assert!(verifying_ring.verify_token(&token).is_ok());
+        assert!(verifying_ring.set_active("2024-01").is_err());
+        assert!(verifying_ring.create_token(&Claims::new()).is_err());
+
+        assert!(matches!(
+            ring.add_signing_key_with_id("2024-01", new_maker()),
+            Err(MakerError::DuplicateKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_token_without_kid() {
+        let first = new_maker();
+        let second = new_maker();
+        let token = second
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+
+        let mut ring = KeyRing::new();
+        ring.add_verifying_key(first.verifier()).unwrap();
+        ring.add_verifying_key(second.verifier()).unwrap();
+        assert!(ring.verify_token(&token).is_ok());
+
+        let mut ring = KeyRing::new();
+        ring.add_verifying_key(first.verifier()).unwrap();
+        assert!(matches!(
+            ring.verify_token(&token),
+            Err(TokenError::Invalid(_))
+        ));
+    }
+}
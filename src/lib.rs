@@ -4,7 +4,7 @@
 //!
 //! # Overview
 //! This library includes modules for defining claims, handling errors, and creating/verifying PASETO tokens.
-//! It leverages the `rusty_paseto` crate and currently supports PASETO Tokens V4.public.
+//! It leverages the `rusty_paseto` crate and currently supports PASETO Tokens V4.public and V4.local.
 //!
 //! # Usage Example
 //! ```rust
@@ -23,13 +23,42 @@
 //! }
 //! ```
 //!
-//! This library uses the `rusty_paseto` crate underneath and currently only supports PASETO Tokens V4.public.
+//! This library uses the `rusty_paseto` crate underneath and currently only supports PASETO Tokens V4.public and V4.local.
 mod claims;
+mod clock;
 pub mod errors;
+mod footer;
+mod jwk;
+mod keyring;
 mod maker;
+mod paserk;
+mod replay;
+mod revocation;
+mod session;
+mod token;
 
-pub use claims::Claims;
-pub use maker::Maker;
+pub use claims::{Claims, PasetoClaims};
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use footer::Footer;
+pub use jwk::{Jwk, JwkSet};
+pub use keyring::KeyRing;
+pub use maker::{Maker, MakerConfig, TokenIdGenerator, Verifier};
+pub use replay::{MemoryReplayStore, ReplayStore};
+pub use revocation::{FileRevocationStore, MemoryRevocationStore, RevocationStore};
+pub use session::{MemorySessionStore, Rotation, SessionManager, SessionStore, TokenPair};
+pub use token::{SignedToken, Token, TokenInput};
+pub use validation::Validation;
+
+/// Derives [`PasetoClaims`] for a struct with named fields. Requires the `derive` feature.
+#[cfg(feature = "derive")]
+pub use paseto_maker_derive::PasetoClaims;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::claims::claim;
+    pub use chrono;
+}
 
 pub mod purpose;
+mod validation;
 pub mod version;
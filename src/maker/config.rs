@@ -0,0 +1,218 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{claims::reserved, Claims};
+
+/// The lifetime of tokens created without an `exp` claim or a configured lifetime.
+const DEFAULT_LIFETIME: Duration = Duration::hours(1);
+
+/// Generates the `jti` claim of tokens that do not set one.
+#[derive(Debug, Clone, Copy)]
+pub enum TokenIdGenerator {
+    /// A random UUID, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    UuidV4,
+    /// A time-ordered UUID.
+    UuidV7,
+    /// A time-ordered [ULID](https://github.com/ulid/spec), e.g. `01ARZ3NDEKTSV4RRFFQ69G5FAV`.
+    Ulid,
+    /// An application-defined generator.
+    Custom(fn() -> String),
+}
+
+impl TokenIdGenerator {
+    #[must_use]
+    pub fn generate(&self) -> String {
+        match self {
+            Self::UuidV4 => uuid::Uuid::new_v4().to_string(),
+            Self::UuidV7 => uuid::Uuid::now_v7().to_string(),
+            Self::Ulid => ulid::Ulid::new().to_string(),
+            Self::Custom(generate) => generate(),
+        }
+    }
+}
+
+/// Default claims a `Maker` adds to every token it creates.
+///
+/// A claim is only added when the caller's `Claims` leave it out, so each default can be overridden per token.
+/// The `iat` claim defaults to the creation time as read from the maker's [`Clock`](crate::Clock), and `exp` to
+/// one hour after creation unless another lifetime is configured. No `nbf` claim is added.
+///
+/// Because every token carries an `iat`, a verifier whose clock runs behind the maker's rejects fresh tokens with
+/// [`TokenError::IssuedInFuture`](crate::errors::TokenError::IssuedInFuture) under the default leeway of zero.
+/// Verifiers on other hosts should allow for clock skew with `with_leeway`, e.g. a few seconds.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, MakerConfig, Claims, TokenIdGenerator};
+/// use chrono::Duration;
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker").with_config(
+///     MakerConfig::new()
+///         .with_issuer("auth.example.com")
+///         .with_audience("api")
+///         .with_lifetime(Duration::minutes(15))
+///         .with_token_identifier(TokenIdGenerator::UuidV7),
+/// );
+///
+/// let token = maker.create_token(&Claims::new().with_subject("user")).unwrap();
+/// let claims = maker.verify_token(&token).unwrap();
+/// assert_eq!(claims.get_issuer(), Some("auth.example.com".to_string()));
+/// assert!(claims.get_token_identifier().is_some());
+///
+/// let token = maker.create_token(&Claims::new().with_issuer("other")).unwrap();
+/// let claims = maker.verify_token(&token).unwrap();
+/// assert_eq!(claims.get_issuer(), Some("other".to_string()));
+/// ```
+///
+/// # Methods
+///
+/// - `new`: Creates a `MakerConfig` that only sets the time claims.
+/// - `with_issuer`: Sets the default `iss` claim.
+/// - `with_audience`: Sets the default `aud` claim.
+/// - `with_lifetime`: Sets the default `exp` claim relative to the creation time.
+/// - `with_token_identifier`: Generates a `jti` claim with the given generator.
+#[derive(Debug, Clone, Default)]
+pub struct MakerConfig {
+    issuer: Option<String>,
+    audience: Option<String>,
+    lifetime: Option<Duration>,
+    token_identifier: Option<TokenIdGenerator>,
+}
+
+impl MakerConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_issuer<T: AsRef<str>>(mut self, issuer: T) -> Self {
+        self.issuer = Some(issuer.as_ref().to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_audience<T: AsRef<str>>(mut self, audience: T) -> Self {
+        self.audience = Some(audience.as_ref().to_string());
+        self
+    }
+
+    #[must_use]
+    pub const fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_token_identifier(mut self, generator: TokenIdGenerator) -> Self {
+        self.token_identifier = Some(generator);
+        self
+    }
+
+    /// Returns `claims` with every default the caller left out filled in.
+    pub(crate) fn apply(&self, claims: &Claims, now: DateTime<Utc>) -> Claims {
+        let mut claims = claims.clone();
+        if !claims.contains(reserved::ISSUED_AT) {
+            claims = claims.with_issued_at_time(now);
+        }
+        if !claims.contains(reserved::EXPIRATION) {
+            claims = claims.with_expiration_time(now + self.lifetime.unwrap_or(DEFAULT_LIFETIME));
+        }
+        if let (Some(issuer), false) = (&self.issuer, claims.contains(reserved::ISSUER)) {
+            claims = claims.with_issuer(issuer);
+        }
+        if let (Some(audience), false) = (&self.audience, claims.contains(reserved::AUDIENCE)) {
+            claims = claims.with_audience(audience);
+        }
+        if let (Some(generator), false) = (
+            self.token_identifier,
+            claims.contains(reserved::TOKEN_IDENTIFIER),
+        ) {
+            claims = claims.with_token_identifier(generator.generate());
+        }
+        claims
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-09-18T03:42:15+00:00")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let config = MakerConfig::new()
+            .with_issuer("issuer")
+            .with_audience("audience")
+            .with_lifetime(Duration::minutes(15))
+            .with_token_identifier(TokenIdGenerator::UuidV4);
+
+        let claims = config.apply(&Claims::new().with_subject("user"), now());
+        assert_eq!(claims.get_subject(), Some("user".to_string()));
+        assert_eq!(claims.get_issued_at(), Some(now()));
+        assert_eq!(claims.get_expiration(), Some(now() + Duration::minutes(15)));
+        assert_eq!(claims.get_issuer(), Some("issuer".to_string()));
+        assert_eq!(claims.get_audience(), Some("audience".to_string()));
+        assert!(claims.get_token_identifier().is_some());
+
+        let other = config.apply(&Claims::new(), now());
+        assert_ne!(other.get_token_identifier(), claims.get_token_identifier());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let config = MakerConfig::new()
+            .with_issuer("issuer")
+            .with_audience("audience")
+            .with_lifetime(Duration::minutes(15))
+            .with_token_identifier(TokenIdGenerator::Ulid);
+
+        let issued_at = now() - Duration::minutes(5);
+        let claims = Claims::new()
+            .with_issuer("other")
+            .with_audience("other")
+            .with_issued_at_time(issued_at)
+            .with_token_identifier("token_id");
+        let claims = config.apply(&claims, now());
+        assert_eq!(claims.get_issuer(), Some("other".to_string()));
+        assert_eq!(claims.get_audience(), Some("other".to_string()));
+        assert_eq!(claims.get_issued_at(), Some(issued_at));
+        assert_eq!(claims.get_expiration(), Some(now() + Duration::minutes(15)));
+        assert_eq!(claims.get_token_identifier(), Some("token_id".to_string()));
+
+        let claims = config.apply(&Claims::new().with_expiration_time(now()), now());
+        assert_eq!(claims.get_expiration(), Some(now()));
+    }
+
+    #[test]
+    fn test_no_defaults() {
+        let claims = MakerConfig::new().apply(&Claims::new(), now());
+        assert_eq!(claims.iter().count(), 2);
+        assert_eq!(claims.get_issued_at(), Some(now()));
+        assert_eq!(claims.get_not_before(), None);
+        assert_eq!(claims.get_expiration(), Some(now() + DEFAULT_LIFETIME));
+    }
+
+    #[test]
+    fn test_token_id_generators() {
+        let uuid = TokenIdGenerator::UuidV4.generate();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(uuid.as_bytes()[14], b'4');
+
+        let uuid = TokenIdGenerator::UuidV7.generate();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(uuid.as_bytes()[14], b'7');
+
+        assert_eq!(TokenIdGenerator::Ulid.generate().len(), 26);
+        assert_eq!(
+            TokenIdGenerator::Custom(|| "custom".to_string()).generate(),
+            "custom"
+        );
+    }
+}
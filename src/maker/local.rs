@@ -0,0 +1,211 @@
+use rusty_paseto::core::Key;
+
+use super::Maker;
+use crate::{errors::MakerError, paserk, purpose::Local, version::V4};
+
+/// `Maker<V4, Local>` creates and verifies encrypted `v4.local` PASETO tokens with a shared symmetric key.
+///
+/// # Methods
+///
+/// - `new_local(key: &[u8; 32]) -> Self`
+///   - Creates a new `Maker` instance with the given symmetric key.
+/// - `new_local_key() -> [u8; 32]`
+///   - Generates a new random 32-byte symmetric key.
+/// - `create_token(&self, claims: &Claims) -> Result<SignedToken<V4, Local>, TokenError>`
+///   - Creates a new encrypted PASETO token with the given claims.
+/// - `verify_token(&self, token: impl TokenInput<V4, P>) -> Result<Claims, TokenError>`
+///   - Decrypts and verifies a PASETO token. Returns the containing Claims or an error if the token verification fails.
+///
+/// # Example
+///
+/// ```rust
+/// use paseto_maker::{Maker, Claims};
+/// let key = Maker::new_local_key();
+/// let maker = Maker::new_local(&key);
+/// let claims = Claims::new().with_subject("example");
+/// let token = maker.create_token(&claims).unwrap();
+/// assert!(token.as_str().starts_with("v4.local."));
+/// ```
+impl Maker<V4, Local> {
+    #[must_use]
+    pub fn new_local(key: &[u8; 32]) -> Self {
+        Self::from_keys(Key::<32>::from(key), Key::<32>::from(key))
+    }
+
+    /// Creates a new `Maker` from a `k4.local` PASERK.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the PASERK is malformed.
+    pub fn from_paserk_local(paserk: &str) -> Result<Self, MakerError> {
+        Ok(Self::new_local(&paserk::decode(paserk::LOCAL, paserk)?))
+    }
+
+    /// Returns the symmetric key as a `k4.local` PASERK.
+    #[must_use]
+    pub fn to_paserk_local(&self) -> String {
+        paserk::encode(paserk::LOCAL, self.key_as_bytes())
+    }
+
+    #[must_use]
+    pub fn new_local_key() -> [u8; 32] {
+        let mut key = [0u8; 32];
+        rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut key);
+        key
+    }
+
+    #[must_use]
+    pub fn key_as_bytes(&self) -> &[u8; 32] {
+        &self.key
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+    use crate::{errors::TokenError, Claims, Footer};
+
+    #[test]
+    fn test_create_local_token() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+
+        let mut claims = Claims::new().with_subject("this is the subject");
+        claims.set_claim("data", "secret").unwrap();
+
+        let token = maker
+            .create_token(&claims)
+            .expect("failed to generate token");
+        assert!(token.as_str().starts_with("v4.local."));
+
+        let got = maker.verify_token(&token).expect("failed to verify token");
+        assert_eq!(got.get_subject().unwrap().as_str(), "this is the subject");
+        assert_eq!(got.get_claim::<String>("data").unwrap().as_str(), "secret");
+    }
+
+    #[test]
+    fn test_local_token_wrong_key() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let other = Maker::new_local(&Maker::new_local_key());
+
+        let token = maker
+            .create_token(&Claims::new().with_subject("subject"))
+            .expect("failed to generate token");
+
+        assert!(matches!(
+            other.verify_token(&token),
+            Err(TokenError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_local_footer() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let footer = Footer::json(serde_json::json!({ "kid": "local-1" })).unwrap();
+
+        let token = maker
+            .create_token_with_footer(&Claims::new().with_subject("subject"), &footer)
+            .expect("failed to generate token");
+
+        let (claims, got) = maker
+            .verify_token_with_footer_check(&token, |got| got == Some(&footer))
+            .expect("failed to verify token");
+        assert_eq!(claims.get_subject().unwrap().as_str(), "subject");
+        assert_eq!(got, Some(footer));
+    }
+
+    #[test]
+    fn test_local_implicit_assertion() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+
+        let token = maker
+            .create_token_with_assertion(&Claims::new().with_subject("subject"), "tenant-1")
+            .expect("failed to generate token");
+
+        assert!(maker
+            .verify_token_with_assertion(&token, "tenant-1")
+            .is_ok());
+        assert!(maker
+            .verify_token_with_assertion(&token, "tenant-2")
+            .is_err());
+        assert!(maker.verify_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_local_expired_token() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let expiration = Utc::now() - Duration::seconds(30);
+
+        let token = maker
+            .create_token(&Claims::new().with_expiration_time(expiration))
+            .expect("failed to generate token");
+
+        assert!(matches!(
+            maker.verify_token(&token),
+            Err(TokenError::Expired)
+        ));
+        assert!(maker
+            .with_leeway(Duration::minutes(1))
+            .verify_token(&token)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_local_paserk() {
+        let key = Maker::new_local_key();
+        let maker = Maker::new_local(&key);
+
+        let paserk = maker.to_paserk_local();
+        assert!(paserk.starts_with("k4.local."));
+
+        let restored = Maker::from_paserk_local(&paserk).expect("failed to restore maker");
+        assert_eq!(restored.key_as_bytes(), &key);
+
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        assert!(restored.verify_token(&token).is_ok());
+
+        assert!(matches!(
+            Maker::from_paserk_local("k4.secret.AAAA"),
+            Err(MakerError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_local_key_id_footer() {
+        let maker = Maker::new_local(&Maker::new_local_key()).with_key_id_footer();
+        assert!(maker.key_id().starts_with("k4.lid."));
+
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        let (_, footer) = maker
+            .verify_token_with_footer(&token)
+            .expect("failed to verify token");
+        assert_eq!(
+            footer.unwrap().get_claim::<String>("kid"),
+            Some(maker.key_id())
+        );
+
+        let other = Maker::new_local(&Maker::new_local_key());
+        assert!(matches!(
+            other.verify_token(&token),
+            Err(TokenError::KeyIdMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_local_token_rejected_by_public_maker() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let (priv_key, _) = Maker::new_keypair();
+        let public = Maker::new(&priv_key).expect("failed to create maker");
+
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+
+        assert!(public.verify_token(token.as_str()).is_err());
+    }
+}
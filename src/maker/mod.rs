@@ -1,16 +1,22 @@
 #![allow(dead_code)]
-use std::marker::PhantomData;
+use chrono::{DateTime, Duration, Utc};
+use serde::{de::DeserializeOwned, Serialize};
 
 use rusty_paseto::{
-    core::{
-        Key, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, Public as pPublic, V4 as pV4,
-    },
+    core::{Footer as pFooter, ImplicitAssertion, Key, V4 as pV4},
     prelude::{
-        AudienceClaim, CustomClaim, ExpirationClaim, IssuedAtClaim, IssuerClaim, NotBeforeClaim,
-        PasetoBuilder, SubjectClaim, TokenIdentifierClaim,
+        AudienceClaim, CustomClaim, ExpirationClaim, GenericBuilder, IssuedAtClaim, IssuerClaim,
+        NotBeforeClaim, SubjectClaim, TokenIdentifierClaim,
     },
 };
 
+use crate::clock::Clock;
+use crate::footer::{check_key_id, decode_footer, with_key_id, Footer};
+use crate::paserk;
+use crate::replay::ReplayStore;
+use crate::revocation::RevocationStore;
+use crate::token::{SignedToken, TokenInput};
+use crate::validation::Validation;
 use crate::{claims::reserved, purpose::Public, version::V4, Claims};
 use crate::{
     errors::{MakerError, TokenError},
@@ -19,14 +25,92 @@ use crate::{
 };
 // pub mod error;
 
+mod config;
+mod local;
+mod openssh;
+mod pkcs8;
+mod verifier;
+
+pub use config::{MakerConfig, TokenIdGenerator};
+pub use verifier::Verifier;
+
 pub struct Maker<V: Version, P: Purpose> {
-    private_key: Key<64>,
-    public_key: Key<32>,
-    public_key_bytes: [u8; 32],
-    version: String,
-    purpose: String,
-    _version: PhantomData<V>,
-    _purpose: PhantomData<P>,
+    key: P::Key,
+    verifier: Verifier<V, P>,
+    config: MakerConfig,
+    key_id_footer: bool,
+}
+
+impl<V: Version, P: Purpose> Maker<V, P> {
+    fn from_keys(key: P::Key, verifying_key: P::VerifyingKey) -> Self {
+        Self {
+            key,
+            verifier: Verifier::from_key(verifying_key),
+            config: MakerConfig::new(),
+            key_id_footer: false,
+        }
+    }
+
+    /// Sets the clock-skew leeway tolerated when checking the `exp`, `nbf` and `iat` claims of verified tokens.
+    /// The leeway is zero by default, see [`MakerConfig`](crate::MakerConfig) for why a verifier on another host
+    /// should set one.
+    #[must_use]
+    pub fn with_leeway(mut self, leeway: Duration) -> Self {
+        self.verifier = self.verifier.with_leeway(leeway);
+        self
+    }
+
+    /// Sets the [`Validation`] policy applied by `verify_token` and its variants. A leeway set earlier with
+    /// `with_leeway` is kept unless `validation` sets its own.
+    #[must_use]
+    pub fn with_validation(mut self, validation: Validation) -> Self {
+        self.verifier = self.verifier.with_validation(validation);
+        self
+    }
+
+    /// Sets the [`MakerConfig`] whose default claims are added to every created token.
+    #[must_use]
+    pub fn with_config(mut self, config: MakerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the [`Clock`] used for the default time claims of created tokens and for validating verified ones.
+    #[must_use]
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.verifier = self.verifier.with_clock(clock);
+        self
+    }
+
+    /// Sets the [`RevocationStore`] consulted after a token's signature and claims were verified. Revoked tokens are
+    /// rejected with [`TokenError::Revoked`].
+    #[must_use]
+    pub fn with_revocation_store<S: RevocationStore + 'static>(mut self, store: S) -> Self {
+        self.verifier = self.verifier.with_revocation_store(store);
+        self
+    }
+
+    /// Returns the current time of this maker's clock.
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.verifier.now()
+    }
+
+    /// Embeds this maker's PASERK key id as `{"kid": ...}` in the footer of every created token.
+    ///
+    /// Verification always rejects tokens whose footer `kid` is a PASERK ID of a different key, whether or not
+    /// this is set.
+    #[must_use]
+    pub const fn with_key_id_footer(mut self) -> Self {
+        self.key_id_footer = true;
+        self
+    }
+
+    /// Stops embedding the key id, for owners such as a [`KeyRing`](crate::KeyRing) that set the footer `kid`
+    /// themselves.
+    pub(crate) const fn without_key_id_footer(mut self) -> Self {
+        self.key_id_footer = false;
+        self
+    }
 }
 
 /// `Maker` is a struct that provides functionality to create and manage PASETO (Platform-Agnostic Security Tokens) tokens.
@@ -37,9 +121,9 @@ pub struct Maker<V: Version, P: Purpose> {
 ///   - Creates a new `Maker` instance with the given private and public keys.
 /// - `new_keypair() -> ([u8; 64], [u8; 32])`
 ///   - Generates a new Ed25519 keypair and returns the private and public keys.
-/// - `create_token(&self, claims: &Claims) -> Result<String, TokenError>`
-///   - Creates a new PASETO token with the given claims. Returns the token as a `String` or an error if the token creation fails.
-/// - `verify_token(&self, token: &str) -> Result<Claims, TokenError>`
+/// - `create_token(&self, claims: &Claims) -> Result<SignedToken<V4, Public>, TokenError>`
+///   - Creates a new PASETO token with the given claims. Returns the token as a `SignedToken` or an error if the token creation fails.
+/// - `verify_token(&self, token: impl TokenInput<V4, P>) -> Result<Claims, TokenError>`
 ///   - Verifies a PASETO token. Returns the containing Claims or an error if the token verification fails.
 ///
 /// # Example
@@ -58,16 +142,41 @@ impl Maker<V4, Public> {
     pub fn new(private_key: &[u8; 64]) -> Result<Self, MakerError> {
         let private_key = ed25519_dalek::SigningKey::from_keypair_bytes(private_key)
             .map_err(|err| MakerError::InvalidKey(err.to_string()))?;
-        let public_key = private_key.verifying_key().to_bytes();
-        Ok(Self {
-            private_key: Key::<64>::from(&private_key.to_keypair_bytes()),
-            public_key: Key::<32>::from(&public_key),
-            public_key_bytes: public_key,
-            version: V4::NAME.to_string(),
-            purpose: Public::NAME.to_string(),
-            _version: PhantomData,
-            _purpose: PhantomData,
-        })
+        Ok(Self::from_signing_key(&private_key))
+    }
+
+    fn from_signing_key(private_key: &ed25519_dalek::SigningKey) -> Self {
+        Self::from_keys(
+            Key::<64>::from(&private_key.to_keypair_bytes()),
+            private_key.verifying_key().to_bytes(),
+        )
+    }
+
+    /// Creates a new `Maker` from a `k4.secret` PASERK.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the PASERK is malformed or does not contain a valid secret key.
+    pub fn from_paserk(paserk: &str) -> Result<Self, MakerError> {
+        Self::new(&paserk::decode(paserk::SECRET, paserk)?)
+    }
+
+    /// Returns the secret key as a `k4.secret` PASERK.
+    #[must_use]
+    pub fn to_paserk_secret(&self) -> String {
+        paserk::encode(paserk::SECRET, self.key.as_slice())
+    }
+
+    /// Returns the public key as a `k4.public` PASERK.
+    #[must_use]
+    pub fn to_paserk_public(&self) -> String {
+        paserk::encode(paserk::PUBLIC, self.public_key_as_bytes())
+    }
+
+    /// Returns the `k4.sid` PASERK ID of the secret key.
+    #[must_use]
+    pub fn secret_key_id(&self) -> String {
+        paserk::id(paserk::SECRET_ID, &self.to_paserk_secret())
     }
 
     #[must_use]
@@ -78,143 +187,331 @@ impl Maker<V4, Public> {
         (priv_key.to_keypair_bytes(), pub_key.to_bytes())
     }
 
-    fn private_key(&self) -> PasetoAsymmetricPrivateKey<pV4, pPublic> {
-        PasetoAsymmetricPrivateKey::<pV4, pPublic>::from(&self.private_key)
+    #[must_use]
+    pub const fn public_key_as_bytes(&self) -> &[u8; 32] {
+        self.verifier.public_key_as_bytes()
     }
 
-    fn public_key(&self) -> PasetoAsymmetricPublicKey<pV4, pPublic> {
-        PasetoAsymmetricPublicKey::<pV4, pPublic>::from(&self.public_key)
+    /// Returns a [`Verifier`] for this maker's public key, which can be handed to services that must not sign tokens.
+    #[must_use]
+    pub fn verifier(&self) -> Verifier<V4, Public> {
+        self.verifier.clone()
     }
+}
 
+/// Token creation and verification, shared by `V4.public` makers, which sign and verify with an Ed25519 keypair, and
+/// `V4.local` makers, which encrypt and decrypt with a symmetric key. Verification is delegated to the maker's
+/// [`Verifier`].
+impl<P: Purpose> Maker<V4, P> {
+    /// Returns the PASERK ID of this maker's key, `k4.pid` for `V4.public` and `k4.lid` for `V4.local` makers. This is
+    /// the id embedded in token footers.
     #[must_use]
-    pub const fn public_key_as_bytes(&self) -> &[u8; 32] {
-        &self.public_key_bytes
+    pub fn key_id(&self) -> String {
+        self.verifier.key_id()
     }
 
+    /// See [`Verifier::verify_token`].
+    ///
     /// # Errors
     ///
     /// This function will return an error if the token verification fails.
-    pub fn verify_token(&self, token: &str) -> Result<Claims, TokenError> {
-        let public_key = self.public_key();
-        let mut parser = rusty_paseto::prelude::PasetoParser::<pV4, pPublic>::default();
-
-        let token = {
-            parser
-                .parse(token, &public_key)
-                .map_err(|err| TokenError::TokenCreationFailed(err.to_string()))?
-        };
-        Ok(token.into())
+    pub fn verify_token(&self, token: impl TokenInput<V4, P>) -> Result<Claims, TokenError> {
+        self.verifier.verify_token(token)
+    }
+
+    /// See [`Verifier::consume_token`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails or the token cannot be consumed.
+    pub fn consume_token(
+        &self,
+        token: impl TokenInput<V4, P>,
+        store: &dyn ReplayStore,
+    ) -> Result<Claims, TokenError> {
+        self.verifier.consume_token(token, store)
+    }
+
+    /// See [`Verifier::verify_token_as`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails or the claims cannot be deserialized into `T`.
+    pub fn verify_token_as<T: DeserializeOwned>(
+        &self,
+        token: impl TokenInput<V4, P>,
+    ) -> Result<T, TokenError> {
+        self.verifier.verify_token_as(token)
+    }
+
+    /// See [`Verifier::verify_token_with`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails or its claims do not satisfy `validation`.
+    pub fn verify_token_with(
+        &self,
+        token: impl TokenInput<V4, P>,
+        validation: &Validation,
+    ) -> Result<Claims, TokenError> {
+        self.verifier.verify_token_with(token, validation)
+    }
+
+    /// See [`Verifier::verify_token_with_footer`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails.
+    pub fn verify_token_with_footer(
+        &self,
+        token: impl TokenInput<V4, P>,
+    ) -> Result<(Claims, Option<Footer>), TokenError> {
+        self.verifier.verify_token_with_footer(token)
+    }
+
+    /// See [`Verifier::verify_token_with_footer_check`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails or `check` rejects the footer.
+    pub fn verify_token_with_footer_check<F>(
+        &self,
+        token: impl TokenInput<V4, P>,
+        check: F,
+    ) -> Result<(Claims, Option<Footer>), TokenError>
+    where
+        F: FnOnce(Option<&Footer>) -> bool,
+    {
+        self.verifier.verify_token_with_footer_check(token, check)
+    }
+
+    /// See [`Verifier::verify_token_with_assertion`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails, including when the
+    /// implicit assertion does not match the one the token was created with.
+    pub fn verify_token_with_assertion(
+        &self,
+        token: impl TokenInput<V4, P>,
+        implicit_assertion: &str,
+    ) -> Result<Claims, TokenError> {
+        self.verifier
+            .verify_token_with_assertion(token, implicit_assertion)
     }
 
     /// # Errors
     ///
     /// This function will return an error if the token creation fails due to invalid claims or other issues.
     ///
-    pub fn create_token(&self, claims: &Claims) -> Result<String, TokenError> {
-        let mut builder = PasetoBuilder::<pV4, pPublic>::default();
-
-        for (key, value) in claims.iter() {
-            // dbg!(key, format!("{}", value.to_string().trim_matches('"').to_string()));
-            match key.as_ref() {
-                reserved::ISSUER => {
-                    if let Some(issuer) = value.as_str() {
-                        let _ = builder.set_claim(IssuerClaim::from(issuer));
-                    } else {
-                        return Err(TokenError::InvalidClaim("Invalid issuer claim".to_string()));
-                    }
+    pub fn create_token(&self, claims: &Claims) -> Result<SignedToken<V4, P>, TokenError> {
+        self.build_token(claims, None, None)
+    }
+
+    /// Creates a new PASETO token from a serializable value, typically a struct whose reserved claims are
+    /// mapped with serde renames such as `#[serde(rename = "sub")]`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the value is not a valid set of claims or the token creation fails.
+    pub fn create_token_from<T: Serialize>(
+        &self,
+        value: &T,
+    ) -> Result<SignedToken<V4, P>, TokenError> {
+        self.create_token(&Claims::from_serializable(value)?)
+    }
+
+    /// Creates a new PASETO token with the given claims and an unencrypted footer.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token creation fails due to invalid claims or other issues.
+    pub fn create_token_with_footer(
+        &self,
+        claims: &Claims,
+        footer: &Footer,
+    ) -> Result<SignedToken<V4, P>, TokenError> {
+        self.build_token(claims, Some(footer), None)
+    }
+
+    /// Creates a new PASETO token with the given claims, binding `implicit_assertion` into the
+    /// signature or authentication tag without including it in the token.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token creation fails due to invalid claims or other issues.
+    pub fn create_token_with_assertion(
+        &self,
+        claims: &Claims,
+        implicit_assertion: &str,
+    ) -> Result<SignedToken<V4, P>, TokenError> {
+        self.build_token(claims, None, Some(implicit_assertion))
+    }
+
+    fn build_token(
+        &self,
+        claims: &Claims,
+        footer: Option<&Footer>,
+        implicit_assertion: Option<&str>,
+    ) -> Result<SignedToken<V4, P>, TokenError> {
+        let footer = if self.key_id_footer {
+            Some(with_key_id(footer, &self.key_id())?)
+        } else {
+            footer.cloned()
+        };
+        let encoded_footer = footer.as_ref().map(Footer::to_string);
+        let claims = self.config.apply(claims, self.now());
+        let mut builder = GenericBuilder::<pV4, P::Paseto>::new();
+        set_claims(&mut builder, &claims)?;
+        if let Some(footer) = &encoded_footer {
+            builder.set_footer(pFooter::from(footer.as_str()));
+        }
+        if let Some(implicit_assertion) = implicit_assertion {
+            builder.set_implicit_assertion(ImplicitAssertion::from(implicit_assertion));
+        }
+
+        let token = P::build(&mut builder, &self.key)
+            .map_err(|err| TokenError::TokenCreationFailed(err.to_string()))?;
+        Ok(SignedToken::new(token, footer))
+    }
+}
+
+/// Verifies or decrypts a token of purpose `P` with `key` and checks its claims against `validation` at `now`.
+pub(crate) fn parse_token<P: Purpose>(
+    key: &P::VerifyingKey,
+    token: &str,
+    implicit_assertion: Option<&str>,
+    validation: &Validation,
+    now: DateTime<Utc>,
+) -> Result<(Claims, Option<Footer>), TokenError> {
+    let footer = decode_footer(token)?;
+    check_key_id(footer.as_deref(), &P::key_id(key))?;
+    let token = P::parse(token, key, footer.as_deref(), implicit_assertion)?;
+    let claims = Claims::try_from(token)?;
+    validation.validate_at(&claims, now)?;
+    Ok((claims, footer.as_deref().map(Footer::parse)))
+}
+
+/// Applies a footer predicate to an already verified token.
+fn check_footer<F>(
+    claims: Claims,
+    footer: Option<Footer>,
+    check: F,
+) -> Result<(Claims, Option<Footer>), TokenError>
+where
+    F: FnOnce(Option<&Footer>) -> bool,
+{
+    if check(footer.as_ref()) {
+        Ok((claims, footer))
+    } else {
+        Err(TokenError::FooterMismatch)
+    }
+}
+
+/// Copies `claims` onto `builder`, mapping the reserved claims onto their `rusty_paseto` types.
+fn set_claims<'a, V, P>(
+    builder: &mut GenericBuilder<'a, 'a, V, P>,
+    claims: &'a Claims,
+) -> Result<(), TokenError> {
+    for (key, value) in claims.iter() {
+        // dbg!(key, format!("{}", value.to_string().trim_matches('"').to_string()));
+        match key.as_ref() {
+            reserved::ISSUER => {
+                if let Some(issuer) = value.as_str() {
+                    let _ = builder.set_claim(IssuerClaim::from(issuer));
+                } else {
+                    return Err(TokenError::InvalidClaim("Invalid issuer claim".to_string()));
                 }
-                reserved::AUDIENCE => {
-                    if let Some(audience) = value.as_str() {
-                        let _ = builder.set_claim(AudienceClaim::from(audience));
-                    } else {
-                        return Err(TokenError::InvalidClaim(
-                            "Invalid audience claim".to_string(),
-                        ));
-                    }
+            }
+            reserved::AUDIENCE => {
+                if let Some(audience) = value.as_str() {
+                    let _ = builder.set_claim(AudienceClaim::from(audience));
+                } else {
+                    return Err(TokenError::InvalidClaim(
+                        "Invalid audience claim".to_string(),
+                    ));
                 }
-                reserved::SUBJECT => {
-                    if let Some(subject) = value.as_str() {
-                        let _ = builder.set_claim(SubjectClaim::from(subject));
-                    } else {
-                        return Err(TokenError::InvalidClaim(
-                            "Invalid subject claim".to_string(),
-                        ));
-                    }
+            }
+            reserved::SUBJECT => {
+                if let Some(subject) = value.as_str() {
+                    let _ = builder.set_claim(SubjectClaim::from(subject));
+                } else {
+                    return Err(TokenError::InvalidClaim(
+                        "Invalid subject claim".to_string(),
+                    ));
                 }
-                reserved::ISSUED_AT => {
-                    if let Some(issued_at) = value.as_str() {
-                        match IssuedAtClaim::try_from(issued_at) {
-                            Ok(claim) => {
-                                let _ = builder.set_claim(claim);
-                            }
-                            Err(err) => {
-                                return Err(TokenError::ClaimError(err.into()));
-                            }
+            }
+            reserved::ISSUED_AT => {
+                if let Some(issued_at) = value.as_str() {
+                    match IssuedAtClaim::try_from(issued_at) {
+                        Ok(claim) => {
+                            let _ = builder.set_claim(claim);
                         }
-                    } else {
-                        return Err(TokenError::InvalidClaim(
-                            "Invalid issued at claim".to_string(),
-                        ));
-                    }
-                }
-                reserved::NOT_BEFORE => {
-                    if let Some(not_before) = value.as_str() {
-                        match NotBeforeClaim::try_from(not_before) {
-                            Ok(claim) => {
-                                let _ = builder.set_claim(claim);
-                            }
-                            Err(err) => {
-                                return Err(TokenError::ClaimError(err.into()));
-                            }
+                        Err(err) => {
+                            return Err(TokenError::ClaimError(err.into()));
                         }
-                    } else {
-                        return Err(TokenError::InvalidClaim(
-                            "Invalid not before claim".to_string(),
-                        ));
                     }
+                } else {
+                    return Err(TokenError::InvalidClaim(
+                        "Invalid issued at claim".to_string(),
+                    ));
                 }
-                reserved::EXPIRATION => {
-                    if let Some(expiration) = value.as_str() {
-                        match ExpirationClaim::try_from(expiration) {
-                            Ok(claim) => {
-                                let _ = builder.set_claim(claim);
-                            }
-                            Err(err) => {
-                                return Err(TokenError::ClaimError(err.into()));
-                            }
+            }
+            reserved::NOT_BEFORE => {
+                if let Some(not_before) = value.as_str() {
+                    match NotBeforeClaim::try_from(not_before) {
+                        Ok(claim) => {
+                            let _ = builder.set_claim(claim);
+                        }
+                        Err(err) => {
+                            return Err(TokenError::ClaimError(err.into()));
                         }
-                    } else {
-                        return Err(TokenError::InvalidClaim(
-                            "Invalid expiration claim".to_string(),
-                        ));
                     }
+                } else {
+                    return Err(TokenError::InvalidClaim(
+                        "Invalid not before claim".to_string(),
+                    ));
                 }
-                reserved::TOKEN_IDENTIFIER => {
-                    let claim = match value.as_str() {
-                        Some(token_id) => TokenIdentifierClaim::from(token_id),
-                        None => {
-                            return Err(TokenError::InvalidClaim(
-                                "Invalid token identifier claim".to_string(),
-                            ))
+            }
+            reserved::EXPIRATION => {
+                if let Some(expiration) = value.as_str() {
+                    match ExpirationClaim::try_from(expiration) {
+                        Ok(claim) => {
+                            let _ = builder.set_claim(claim);
+                        }
+                        Err(err) => {
+                            return Err(TokenError::ClaimError(err.into()));
                         }
-                    };
-                    let _ = builder.set_claim(claim);
-                }
-                key => match CustomClaim::try_from((key, value)) {
-                    Ok(claim) => {
-                        let _ = builder.set_claim(claim);
                     }
-                    Err(err) => {
-                        return Err(TokenError::InvalidClaim(err.to_string()));
+                } else {
+                    return Err(TokenError::InvalidClaim(
+                        "Invalid expiration claim".to_string(),
+                    ));
+                }
+            }
+            reserved::TOKEN_IDENTIFIER => {
+                let claim = match value.as_str() {
+                    Some(token_id) => TokenIdentifierClaim::from(token_id),
+                    None => {
+                        return Err(TokenError::InvalidClaim(
+                            "Invalid token identifier claim".to_string(),
+                        ))
                     }
-                },
+                };
+                let _ = builder.set_claim(claim);
             }
+            key => match CustomClaim::try_from((key, value)) {
+                Ok(claim) => {
+                    let _ = builder.set_claim(claim);
+                }
+                Err(err) => {
+                    return Err(TokenError::InvalidClaim(err.to_string()));
+                }
+            },
         }
-
-        builder
-            .build(&self.private_key())
-            .map_err(|err| TokenError::TokenCreationFailed(err.to_string()))
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -223,20 +520,39 @@ mod test {
     use std::{
         fs::File,
         io::{Read, Write},
+        sync::Arc,
     };
 
-    use rusty_paseto::prelude::PasetoParser;
+    use rusty_paseto::{
+        core::{
+            Paseto, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, Payload,
+            Public as pPublic,
+        },
+        prelude::PasetoParser,
+    };
 
     use super::*;
+    use crate::errors::{ClaimError, ValidationError};
 
     #[test]
     fn test_invalid_claims() {
         let (priv_key, _) = Maker::new_keypair();
         let maker = Maker::new(&priv_key).expect("failed to create maker");
 
-        let claims = Claims::new().with_issued_at("invalid RF3339 date");
-        let token = maker.create_token(&claims);
-        assert!(token.is_err());
+        assert!(Claims::new().with_issued_at("invalid RF3339 date").is_err());
+
+        // Only a token signed outside the crate can carry a malformed claim.
+        let private_key = Key::<64>::from(&priv_key);
+        let token = Paseto::<pV4, pPublic>::builder()
+            .set_payload(Payload::from(r#"{"iat":"invalid RF3339 date"}"#))
+            .try_sign(&PasetoAsymmetricPrivateKey::<pV4, pPublic>::from(
+                &private_key,
+            ))
+            .expect("failed to sign token");
+        assert!(matches!(
+            maker.verify_token(&token),
+            Err(TokenError::ClaimError(ClaimError::InvalidReservedClaim(key, _))) if key == "iat"
+        ));
     }
 
     #[test]
@@ -244,8 +560,11 @@ mod test {
         let (priv_key, _) = Maker::new_keypair();
         let maker = Maker::new(&priv_key).expect("failed to create maker");
 
-        let public_key = maker.public_key();
-        let mut claims = Claims::new().with_issued_at("2027-09-18T03:42:15+02:00");
+        let public_key = Key::<32>::from(maker.public_key_as_bytes());
+        let public_key = PasetoAsymmetricPublicKey::<pV4, pPublic>::from(&public_key);
+        let mut claims = Claims::new()
+            .with_issued_at("2024-09-18T03:42:15+02:00")
+            .unwrap();
         claims.set_claim("sub", "this is the subject").unwrap();
         claims.set_claim("data", "test").unwrap();
         claims.set_claim("number", 2).unwrap();
@@ -259,7 +578,7 @@ mod test {
         assert_eq!(got.get_subject().unwrap().as_str(), "this is the subject");
         let mut parser = PasetoParser::<pV4, pPublic>::default();
         let token = parser
-            .parse(&token, &public_key)
+            .parse(token.as_str(), &public_key)
             .expect("failed to parse token");
         dbg!(&token);
 
@@ -274,6 +593,426 @@ mod test {
         assert_eq!(token.get("data").unwrap(), "test");
     }
 
+    #[test]
+    fn test_footer() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let footer = Footer::json(serde_json::json!({ "kid": "key-1" })).unwrap();
+
+        let token = maker
+            .create_token_with_footer(&Claims::new().with_subject("subject"), &footer)
+            .expect("failed to generate token");
+
+        let (claims, got) = maker
+            .verify_token_with_footer(&token)
+            .expect("failed to verify token");
+        assert_eq!(claims.get_subject().unwrap().as_str(), "subject");
+        assert_eq!(got.as_ref(), Some(&footer));
+        assert!(maker.verify_token(&token).is_ok());
+
+        let raw = Footer::raw("raw footer");
+        let token = maker
+            .create_token_with_footer(&Claims::new(), &raw)
+            .expect("failed to generate token");
+        let (_, got) = maker
+            .verify_token_with_footer(&token)
+            .expect("failed to verify token");
+        assert_eq!(got, Some(raw));
+
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        let (_, got) = maker
+            .verify_token_with_footer(&token)
+            .expect("failed to verify token");
+        assert_eq!(got, None);
+    }
+
+    #[test]
+    fn test_footer_check() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let footer = Footer::json(serde_json::json!({ "kid": "key-1" })).unwrap();
+        let token = maker
+            .create_token_with_footer(&Claims::new(), &footer)
+            .expect("failed to generate token");
+
+        assert!(maker
+            .verify_token_with_footer_check(&token, |got| got == Some(&footer))
+            .is_ok());
+        assert!(maker
+            .verify_token_with_footer_check(&token, |got| {
+                got.and_then(|f| f.get_claim::<String>("kid")).as_deref() == Some("key-1")
+            })
+            .is_ok());
+        assert!(matches!(
+            maker.verify_token_with_footer_check(&token, |got| got.is_none()),
+            Err(TokenError::FooterMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_tampered_footer() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let token = maker
+            .create_token_with_footer(&Claims::new(), &Footer::raw("original"))
+            .expect("failed to generate token");
+
+        let (body, _) = token.as_str().rsplit_once('.').unwrap();
+        let tampered = format!("{body}.dGFtcGVyZWQ");
+        assert!(maker.verify_token(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_implicit_assertion() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let assertion = r#"{"tenant":"acme","cert":"sha256:abcd"}"#;
+
+        let token = maker
+            .create_token_with_assertion(&Claims::new().with_subject("subject"), assertion)
+            .expect("failed to generate token");
+        assert!(!token.as_str().contains("acme"));
+
+        let claims = maker
+            .verify_token_with_assertion(&token, assertion)
+            .expect("failed to verify token");
+        assert_eq!(claims.get_subject().unwrap().as_str(), "subject");
+
+        assert!(maker.verify_token(&token).is_err());
+        assert!(maker
+            .verify_token_with_assertion(&token, r#"{"tenant":"other","cert":"sha256:abcd"}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_time_claims() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let now = Utc::now();
+
+        let expired = maker
+            .create_token(&Claims::new().with_expiration_time(now - Duration::seconds(30)))
+            .expect("failed to generate token");
+        assert!(matches!(
+            maker.verify_token(&expired),
+            Err(TokenError::Expired)
+        ));
+
+        let not_yet_valid = maker
+            .create_token(&Claims::new().with_not_before_time(now + Duration::seconds(30)))
+            .expect("failed to generate token");
+        assert!(matches!(
+            maker.verify_token(&not_yet_valid),
+            Err(TokenError::NotYetValid)
+        ));
+
+        let issued_in_future = maker
+            .create_token(&Claims::new().with_issued_at_time(now + Duration::seconds(30)))
+            .expect("failed to generate token");
+        assert!(matches!(
+            maker.verify_token(&issued_in_future),
+            Err(TokenError::IssuedInFuture)
+        ));
+
+        let maker = maker.with_leeway(Duration::seconds(60));
+        assert!(maker.verify_token(&expired).is_ok());
+        assert!(maker.verify_token(&not_yet_valid).is_ok());
+        assert!(maker.verify_token(&issued_in_future).is_ok());
+        assert!(maker.verifier().verify_token(&expired).is_ok());
+
+        let maker = maker.with_validation(Validation::new());
+        assert!(maker.verify_token(&expired).is_ok());
+        assert!(maker
+            .verify_token_with(&expired, &Validation::new())
+            .is_ok());
+        let maker = maker.with_validation(Validation::new().with_leeway(Duration::zero()));
+        assert!(matches!(
+            maker.verify_token(&expired),
+            Err(TokenError::Expired)
+        ));
+    }
+
+    #[test]
+    fn test_verify_token_with() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let token = maker
+            .create_token(&Claims::new().with_issuer("issuer").with_audience("api"))
+            .expect("failed to generate token");
+
+        let validation = Validation::new().with_issuer("issuer").with_audience("api");
+        assert!(maker.verify_token_with(&token, &validation).is_ok());
+
+        let validation = Validation::new().with_token_identifier_required();
+        assert!(matches!(
+            maker.verify_token_with(&token, &validation),
+            Err(TokenError::Validation(ValidationError::MissingClaim(_)))
+        ));
+
+        let maker = maker.with_validation(Validation::new().with_audience("admin"));
+        assert!(matches!(
+            maker.verify_token(&token),
+            Err(TokenError::Validation(ValidationError::UnexpectedValue(
+                _,
+                _
+            )))
+        ));
+        assert!(maker.verifier().verify_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_verification_errors() {
+        use std::error::Error;
+
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let token = maker
+            .create_token(&Claims::new().with_subject("subject"))
+            .expect("failed to generate token");
+
+        let (other_key, _) = Maker::new_keypair();
+        let other = Maker::new(&other_key).expect("failed to create maker");
+        let err = other.verify_token(&token).unwrap_err();
+        assert!(matches!(err, TokenError::Invalid(_)));
+        assert!(err.source().is_some());
+
+        let local = Maker::new_local(&Maker::new_local_key())
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        let err = maker.verify_token(local.as_str()).unwrap_err();
+        assert!(matches!(err, TokenError::WrongHeader(_)));
+        assert!(err.source().is_some());
+
+        let err = maker.verify_token("v4.public.!!!").unwrap_err();
+        assert!(matches!(err, TokenError::Format(_)));
+        assert!(err.source().is_some());
+
+        assert!(matches!(
+            maker.verify_token("not a token"),
+            Err(TokenError::Format(_))
+        ));
+        assert!(matches!(
+            maker.verify_token(format!("{token}.!!")),
+            Err(TokenError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_clock() {
+        let clock = crate::FixedClock::new(
+            DateTime::parse_from_rfc3339("2020-01-01T00:00:00+00:00")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key)
+            .expect("failed to create maker")
+            .with_clock(clock.clone())
+            .with_config(MakerConfig::new().with_lifetime(Duration::minutes(5)));
+
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        let claims = maker.verify_token(&token).expect("failed to verify token");
+        assert_eq!(claims.get_issued_at(), Some(clock.now()));
+        assert_eq!(claims.get_not_before(), None);
+        assert_eq!(
+            claims.get_expiration(),
+            Some(clock.now() + Duration::minutes(5))
+        );
+
+        let verifier = maker.verifier();
+        clock.advance(Duration::minutes(4));
+        assert!(verifier.verify_token(&token).is_ok());
+        clock.advance(Duration::minutes(1));
+        assert!(matches!(
+            maker.verify_token(&token),
+            Err(TokenError::Expired)
+        ));
+        assert!(matches!(
+            verifier.verify_token(&token),
+            Err(TokenError::Expired)
+        ));
+
+        clock.advance(-Duration::minutes(10));
+        assert!(matches!(
+            maker.verify_token(&token),
+            Err(TokenError::IssuedInFuture)
+        ));
+    }
+
+    #[test]
+    fn test_revocation() {
+        let clock = crate::FixedClock::new(
+            DateTime::parse_from_rfc3339("2024-01-01T00:00:00.100+00:00")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        let store = Arc::new(crate::MemoryRevocationStore::new());
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key)
+            .expect("failed to create maker")
+            .with_clock(clock.clone())
+            .with_revocation_store(Arc::clone(&store));
+        let verifier = maker.verifier();
+
+        let token = maker
+            .create_token(&Claims::new().with_subject("user"))
+            .expect("failed to generate token");
+        assert!(verifier.verify_token(&token).is_ok());
+
+        clock.advance(Duration::milliseconds(800));
+        store.revoke_subject("user", clock.now()).unwrap();
+        assert!(matches!(
+            maker.verify_token(&token),
+            Err(TokenError::Revoked)
+        ));
+        assert!(matches!(
+            verifier.verify_token(&token),
+            Err(TokenError::Revoked)
+        ));
+
+        let reissued = maker
+            .create_token(&Claims::new().with_subject("user"))
+            .expect("failed to generate token");
+        assert!(matches!(
+            maker.verify_token(&reissued),
+            Err(TokenError::Revoked)
+        ));
+
+        clock.advance(Duration::seconds(1));
+        let token = maker
+            .create_token(&Claims::new().with_subject("user"))
+            .expect("failed to generate token");
+        assert!(maker.verify_token(&token).is_ok());
+    }
+
+    #[test]
+    fn test_serializable_token() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Session {
+            #[serde(rename = "sub")]
+            user_id: String,
+            #[serde(rename = "exp")]
+            expires_at: chrono::DateTime<Utc>,
+            role: String,
+        }
+
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let session = Session {
+            user_id: "1234567890".to_string(),
+            expires_at: Utc::now() + Duration::hours(1),
+            role: "admin".to_string(),
+        };
+
+        let token = maker
+            .create_token_from(&session)
+            .expect("failed to generate token");
+        assert_eq!(maker.verify_token_as::<Session>(&token).unwrap(), session);
+        assert_eq!(
+            maker.verify_token(&token).unwrap().get_subject(),
+            Some("1234567890".to_string())
+        );
+
+        assert!(matches!(
+            maker.verify_token_as::<Vec<String>>(&token),
+            Err(TokenError::ClaimError(_))
+        ));
+        assert!(maker
+            .create_token_from(&serde_json::json!({ "exp": 5 }))
+            .is_err());
+    }
+
+    #[test]
+    fn test_paserk() {
+        let (priv_key, pub_key) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+
+        let secret = maker.to_paserk_secret();
+        assert!(secret.starts_with("k4.secret."));
+        let public = maker.to_paserk_public();
+        assert!(public.starts_with("k4.public."));
+
+        let restored = Maker::from_paserk(&secret).expect("failed to restore maker");
+        assert_eq!(restored.public_key_as_bytes(), &pub_key);
+        assert_eq!(restored.to_paserk_secret(), secret);
+
+        let token = restored
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        let verifier = Verifier::from_paserk(&public).expect("failed to create verifier");
+        assert!(verifier.verify_token(&token).is_ok());
+        assert_eq!(verifier.to_paserk_public(), public);
+
+        assert!(matches!(
+            Maker::from_paserk(&public),
+            Err(MakerError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Verifier::from_paserk(&secret),
+            Err(MakerError::InvalidKey(_))
+        ));
+        let mut corrupted = priv_key;
+        corrupted[63] ^= 1;
+        assert!(matches!(
+            Maker::from_paserk(&paserk::encode(paserk::SECRET, &corrupted)),
+            Err(MakerError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_key_id_footer() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key)
+            .expect("failed to create maker")
+            .with_key_id_footer();
+        assert!(maker.key_id().starts_with("k4.pid."));
+        assert!(maker.secret_key_id().starts_with("k4.sid."));
+        assert_eq!(maker.key_id(), maker.verifier().key_id());
+
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+        let (_, footer) = maker
+            .verify_token_with_footer(&token)
+            .expect("failed to verify token");
+        assert_eq!(
+            footer.unwrap().get_claim::<String>("kid"),
+            Some(maker.key_id())
+        );
+
+        let footer = Footer::json(serde_json::json!({ "app": "test" })).unwrap();
+        let token = maker
+            .create_token_with_footer(&Claims::new(), &footer)
+            .expect("failed to generate token");
+        let (_, footer) = maker
+            .verify_token_with_footer(&token)
+            .expect("failed to verify token");
+        let footer = footer.unwrap();
+        assert_eq!(footer.get_claim::<String>("app").unwrap(), "test");
+        assert_eq!(footer.get_claim::<String>("kid"), Some(maker.key_id()));
+
+        let (other_key, _) = Maker::new_keypair();
+        let other = Maker::new(&other_key).expect("failed to create maker");
+        assert!(matches!(
+            other.verify_token(&token),
+            Err(TokenError::KeyIdMismatch)
+        ));
+
+        let wrong_kid = Footer::json(serde_json::json!({ "kid": other.key_id() })).unwrap();
+        let token = other
+            .create_token_with_footer(&Claims::new(), &wrong_kid)
+            .expect("failed to generate token");
+        assert!(other.verify_token(&token).is_ok());
+        assert!(matches!(
+            maker.verify_token(&token),
+            Err(TokenError::KeyIdMismatch)
+        ));
+    }
+
     #[test]
     fn test_new_private_key() {
         let new_key = Key::<64>::try_new_random().unwrap();
@@ -0,0 +1,337 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::de::DeserializeOwned;
+
+use super::{check_footer, parse_token};
+use crate::{
+    clock::{Clock, SystemClock},
+    errors::{MakerError, TokenError},
+    footer::Footer,
+    paserk,
+    purpose::{Public, Purpose},
+    replay::{self, ReplayStore},
+    revocation::{check_revoked, RevocationStore},
+    token::TokenInput,
+    validation::Validation,
+    version::{Version, V4},
+    Claims,
+};
+
+pub struct Verifier<V: Version, P: Purpose> {
+    key: P::VerifyingKey,
+    validation: Validation,
+    clock: Arc<dyn Clock>,
+    revocation_store: Option<Arc<dyn RevocationStore>>,
+    _version: PhantomData<V>,
+    _purpose: PhantomData<P>,
+}
+
+impl<V: Version, P: Purpose> Clone for Verifier<V, P> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            validation: self.validation.clone(),
+            clock: Arc::clone(&self.clock),
+            revocation_store: self.revocation_store.clone(),
+            _version: PhantomData,
+            _purpose: PhantomData,
+        }
+    }
+}
+
+impl<V: Version, P: Purpose> Verifier<V, P> {
+    pub(crate) fn from_key(key: P::VerifyingKey) -> Self {
+        Self {
+            key,
+            validation: Validation::new(),
+            clock: Arc::new(SystemClock),
+            revocation_store: None,
+            _version: PhantomData,
+            _purpose: PhantomData,
+        }
+    }
+
+    /// Sets the clock-skew leeway tolerated when checking the `exp`, `nbf` and `iat` claims of verified tokens.
+    /// The leeway is zero by default, see [`MakerConfig`](crate::MakerConfig) for why a verifier on another host
+    /// should set one.
+    #[must_use]
+    pub fn with_leeway(mut self, leeway: Duration) -> Self {
+        self.validation = self.validation.with_leeway(leeway);
+        self
+    }
+
+    /// Sets the [`Validation`] policy applied by `verify_token` and its variants. A leeway set earlier with
+    /// `with_leeway` is kept unless `validation` sets its own.
+    #[must_use]
+    pub fn with_validation(mut self, validation: Validation) -> Self {
+        self.validation = validation.inherit_leeway(&self.validation);
+        self
+    }
+
+    /// Sets the [`Clock`] used for validating the `exp`, `nbf` and `iat` claims of verified tokens.
+    #[must_use]
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Sets the [`RevocationStore`] consulted after a token's signature and claims were verified. Revoked tokens are
+    /// rejected with [`TokenError::Revoked`].
+    #[must_use]
+    pub fn with_revocation_store<S: RevocationStore + 'static>(mut self, store: S) -> Self {
+        self.revocation_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Returns the current time of this verifier's clock.
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+}
+
+/// `Verifier` verifies PASETO tokens with only the public key, so it can be deployed where signing keys must not be present.
+///
+/// # Methods
+///
+/// - `new(public_key: &[u8; 32]) -> Result<Self, MakerError>`
+///   - Creates a new `Verifier` instance with the given Ed25519 public key.
+/// - `public_key_as_bytes(&self) -> &[u8; 32]`
+///   - Returns the public key.
+/// - `verify_token(&self, token: impl TokenInput<V4, P>) -> Result<Claims, TokenError>`
+///   - Verifies a PASETO token. Returns the containing Claims or an error if the token verification fails.
+///
+/// # Example
+///
+/// ```rust
+/// use paseto_maker::{Maker, Verifier, Claims};
+/// let (priv_key, pub_key) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker");
+/// let token = maker.create_token(&Claims::new().with_subject("example")).unwrap();
+///
+/// let verifier = Verifier::new(&pub_key).expect("failed to create verifier");
+/// let claims = verifier.verify_token(&token).unwrap();
+/// assert_eq!(claims.get_subject(), Some("example".to_string()));
+/// ```
+///
+/// A `Verifier` cannot sign tokens:
+///
+/// ```compile_fail
+/// use paseto_maker::{Maker, Verifier, Claims};
+/// let (_, pub_key) = Maker::new_keypair();
+/// let verifier = Verifier::new(&pub_key).unwrap();
+/// let token = verifier.create_token(&Claims::new());
+/// ```
+impl Verifier<V4, Public> {
+    /// # Errors
+    ///
+    /// This function will return an error if the provided public key is not a valid Ed25519 point.
+    pub fn new(public_key: &[u8; 32]) -> Result<Self, MakerError> {
+        ed25519_dalek::VerifyingKey::from_bytes(public_key)
+            .map_err(|err| MakerError::InvalidKey(err.to_string()))?;
+        Ok(Self::from_verified_bytes(public_key))
+    }
+
+    /// Creates a new `Verifier` from a `k4.public` PASERK.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the PASERK is malformed or does not contain a valid public key.
+    pub fn from_paserk(paserk: &str) -> Result<Self, MakerError> {
+        Self::new(&paserk::decode(paserk::PUBLIC, paserk)?)
+    }
+
+    /// Returns the public key as a `k4.public` PASERK.
+    #[must_use]
+    pub fn to_paserk_public(&self) -> String {
+        paserk::encode(paserk::PUBLIC, self.public_key_as_bytes())
+    }
+
+    pub(crate) fn from_verified_bytes(public_key: &[u8; 32]) -> Self {
+        Self::from_key(*public_key)
+    }
+
+    #[must_use]
+    pub const fn public_key_as_bytes(&self) -> &[u8; 32] {
+        &self.key
+    }
+}
+
+/// Token verification, shared by `Verifier<V4, Public>` and the verifier every [`Maker`](crate::Maker) delegates
+/// to, which decrypts `V4.local` tokens with the maker's symmetric key.
+impl<P: Purpose> Verifier<V4, P> {
+    /// Returns the PASERK ID of the key tokens are verified with, `k4.pid` for a `V4.public` key.
+    #[must_use]
+    pub fn key_id(&self) -> String {
+        P::key_id(&self.key)
+    }
+
+    /// Verifies a token against this verifier's key and clock, then checks its revocation store.
+    fn verify(
+        &self,
+        token: &str,
+        implicit_assertion: Option<&str>,
+        validation: &Validation,
+    ) -> Result<(Claims, Option<Footer>), TokenError> {
+        let (claims, footer) = parse_token::<P>(
+            &self.key,
+            token,
+            implicit_assertion,
+            validation,
+            self.clock.now(),
+        )?;
+        if let Some(store) = &self.revocation_store {
+            check_revoked(store.as_ref(), &claims)?;
+        }
+        Ok((claims, footer))
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails.
+    pub fn verify_token(&self, token: impl TokenInput<V4, P>) -> Result<Claims, TokenError> {
+        self.verify(token.as_token(), None, &self.validation)
+            .map(|(claims, _)| claims)
+    }
+
+    /// Verifies a PASETO token and deserializes its claims into `T`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails or the claims cannot be deserialized into `T`.
+    pub fn verify_token_as<T: DeserializeOwned>(
+        &self,
+        token: impl TokenInput<V4, P>,
+    ) -> Result<T, TokenError> {
+        Ok(self.verify_token(token)?.deserialize_into()?)
+    }
+
+    /// Verifies a one-time token and marks its `jti` as consumed in `store`, which keeps it until the token's `exp`
+    /// plus the leeway of this verifier's [`Validation`] policy.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails, the token lacks a `jti` or `exp` claim,
+    /// or it was consumed before, in which case the error is [`TokenError::Replayed`].
+    pub fn consume_token(
+        &self,
+        token: impl TokenInput<V4, P>,
+        store: &dyn ReplayStore,
+    ) -> Result<Claims, TokenError> {
+        replay::consume(
+            store,
+            self.verify_token(token)?,
+            self.validation.leeway(),
+            self.clock.now(),
+        )
+    }
+
+    /// Verifies a PASETO token and checks its claims against `validation` instead of this verifier's policy. The
+    /// verifier's leeway applies unless `validation` sets its own.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails or its claims do not satisfy `validation`.
+    pub fn verify_token_with(
+        &self,
+        token: impl TokenInput<V4, P>,
+        validation: &Validation,
+    ) -> Result<Claims, TokenError> {
+        let validation = validation.clone().inherit_leeway(&self.validation);
+        self.verify(token.as_token(), None, &validation)
+            .map(|(claims, _)| claims)
+    }
+
+    /// Verifies a PASETO token and returns its claims together with its footer, if any.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails.
+    pub fn verify_token_with_footer(
+        &self,
+        token: impl TokenInput<V4, P>,
+    ) -> Result<(Claims, Option<Footer>), TokenError> {
+        self.verify(token.as_token(), None, &self.validation)
+    }
+
+    /// Verifies a PASETO token that was created with the given implicit assertion.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails, including when the
+    /// implicit assertion does not match the one the token was created with.
+    pub fn verify_token_with_assertion(
+        &self,
+        token: impl TokenInput<V4, P>,
+        implicit_assertion: &str,
+    ) -> Result<Claims, TokenError> {
+        self.verify(token.as_token(), Some(implicit_assertion), &self.validation)
+            .map(|(claims, _)| claims)
+    }
+
+    /// Verifies a PASETO token and checks its footer with `check`. To require an exact footer, compare
+    /// against it in the predicate, e.g. `|footer| footer == Some(&expected)`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails or `check` rejects the footer.
+    pub fn verify_token_with_footer_check<F>(
+        &self,
+        token: impl TokenInput<V4, P>,
+        check: F,
+    ) -> Result<(Claims, Option<Footer>), TokenError>
+    where
+        F: FnOnce(Option<&Footer>) -> bool,
+    {
+        let (claims, footer) = self.verify_token_with_footer(token)?;
+        check_footer(claims, footer, check)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Maker;
+
+    #[test]
+    fn test_verifier_from_public_key() {
+        let (priv_key, pub_key) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let verifier = Verifier::new(&pub_key).expect("failed to create verifier");
+        assert_eq!(verifier.public_key_as_bytes(), maker.public_key_as_bytes());
+
+        let token = maker
+            .create_token(&Claims::new().with_subject("this is the subject"))
+            .expect("failed to generate token");
+
+        let got = verifier
+            .verify_token(&token)
+            .expect("failed to verify token");
+        assert_eq!(got.get_subject().unwrap().as_str(), "this is the subject");
+    }
+
+    #[test]
+    fn test_verifier_rejects_foreign_token() {
+        let (priv_key, _) = Maker::new_keypair();
+        let (_, other_pub_key) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let verifier = Verifier::new(&other_pub_key).expect("failed to create verifier");
+
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+
+        assert!(verifier.verify_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_maker_verifier() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).expect("failed to create maker");
+        let token = maker
+            .create_token(&Claims::new())
+            .expect("failed to generate token");
+
+        assert!(maker.verifier().verify_token(&token).is_ok());
+    }
+}
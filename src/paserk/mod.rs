@@ -0,0 +1,139 @@
+//! Encoding and decoding of V4 keys in the [PASERK](https://github.com/paseto-standard/paserk) format.
+use crate::errors::MakerError;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use blake2::{
+    digest::{Update, VariableOutput},
+    Blake2bVar,
+};
+
+pub(crate) const LOCAL: &str = "k4.local.";
+pub(crate) const PUBLIC: &str = "k4.public.";
+pub(crate) const SECRET: &str = "k4.secret.";
+
+pub(crate) const LOCAL_ID: &str = "k4.lid.";
+pub(crate) const PUBLIC_ID: &str = "k4.pid.";
+pub(crate) const SECRET_ID: &str = "k4.sid.";
+
+/// Serializes raw key bytes as a PASERK with the given header, e.g. `k4.public.`.
+pub(crate) fn encode(header: &str, key: &[u8]) -> String {
+    format!("{header}{}", URL_SAFE_NO_PAD.encode(key))
+}
+
+/// Parses a PASERK with the given header into raw key bytes of the expected length.
+pub(crate) fn decode<const N: usize>(header: &str, paserk: &str) -> Result<[u8; N], MakerError> {
+    let encoded = paserk.strip_prefix(header).ok_or_else(|| {
+        MakerError::InvalidKey(match paserk_header(paserk) {
+            Some(found) => format!("expected a PASERK starting with '{header}', got '{found}'"),
+            None => format!(
+                "expected a PASERK starting with '{header}', got a value that is not a PASERK"
+            ),
+        })
+    })?;
+    let key = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|err| MakerError::InvalidKey(format!("invalid PASERK encoding: {err}")))?;
+    let len = key.len();
+    key.try_into().map_err(|_| {
+        MakerError::InvalidKey(format!(
+            "expected a {N} byte key in '{header}', got {len} bytes"
+        ))
+    })
+}
+
+/// Computes the PASERK ID with the given header, e.g. `k4.pid.`, of a serialized PASERK.
+pub(crate) fn id(header: &str, paserk: &str) -> String {
+    let mut hasher = Blake2bVar::new(33).expect("33 bytes is a valid BLAKE2b output size");
+    hasher.update(header.as_bytes());
+    hasher.update(paserk.as_bytes());
+    let mut digest = [0u8; 33];
+    hasher
+        .finalize_variable(&mut digest)
+        .expect("output buffer matches the BLAKE2b output size");
+    encode(header, &digest)
+}
+
+/// Returns the `k<version>.<type>.` header of a PASERK without exposing any key material, or `None` if `paserk`
+/// does not start with such a header.
+fn paserk_header(paserk: &str) -> Option<&str> {
+    let mut parts = paserk.splitn(3, '.');
+    let (Some(version), Some(kind), Some(_)) = (parts.next(), parts.next(), parts.next()) else {
+        return None;
+    };
+    let is_version = version
+        .strip_prefix('k')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()));
+    let is_type = !kind.is_empty() && kind.bytes().all(|b| b.is_ascii_lowercase() || b == b'-');
+    (is_version && is_type).then(|| &paserk[..version.len() + kind.len() + 2])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode_local() {
+        let key: Vec<u8> = (0..32).collect();
+        assert_eq!(
+            encode(LOCAL, &key),
+            "k4.local.AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let paserk = encode(PUBLIC, &key);
+        assert_eq!(decode::<32>(PUBLIC, &paserk).unwrap(), key);
+    }
+
+    fn hex(key: &str) -> Vec<u8> {
+        (0..key.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&key[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_ids() {
+        let key = hex("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f");
+        assert_eq!(
+            id(LOCAL_ID, &encode(LOCAL, &key)),
+            "k4.lid.iVtYQDjr5gEijCSjJC3fQaJm7nCeQSeaty0Jixy8dbsk"
+        );
+        assert_eq!(
+            id(PUBLIC_ID, &encode(PUBLIC, &key)),
+            "k4.pid.9ShR3xc8-qVJ_di0tc9nx0IDIqbatdeM2mqLFBJsKRHs"
+        );
+
+        let key = hex("707172737475767778797a7b7c7d7e7f808182838485868788898a8b8c8d8e8f1ce56a48c82ff99162a14bc544612674e5d61fb9317e65d4055780fdbcb4dc35");
+        assert_eq!(
+            encode(SECRET, &key),
+            "k4.secret.cHFyc3R1dnd4eXp7fH1-f4CBgoOEhYaHiImKi4yNjo8c5WpIyC_5kWKhS8VEYSZ05dYfuTF-ZdQFV4D9vLTcNQ"
+        );
+        assert_eq!(
+            id(SECRET_ID, &encode(SECRET, &key)),
+            "k4.sid.gHYyx8y5YzqKEZeYoMDqUOKejdSnY_AWhYZiSCMjR1V5"
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let key = encode(LOCAL, &[0u8; 32]);
+        let err = decode::<32>(PUBLIC, &key).unwrap_err().to_string();
+        assert!(err.contains("k4.local."));
+        assert!(!err.contains("AAAA"));
+
+        let bare = URL_SAFE_NO_PAD.encode([7u8; 32]);
+        let err = decode::<32>(LOCAL, &bare).unwrap_err().to_string();
+        assert!(err.contains("not a PASERK"));
+        assert!(!err.contains(&bare));
+        let err = decode::<32>(LOCAL, "secret.material.here")
+            .unwrap_err()
+            .to_string();
+        assert!(!err.contains("secret"));
+
+        assert!(decode::<32>(LOCAL, "k4.local.!!").is_err());
+        assert!(decode::<32>(LOCAL, &encode(LOCAL, &[0u8; 16])).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use rusty_paseto::{
+    core::{Key, Local as pLocal, PasetoSymmetricKey, V4 as pV4},
+    prelude::{GenericBuilder, GenericBuilderError, GenericParserError},
+};
+use serde_json::Value;
+
+use super::{
+    sealed::{parser, PasetoPurpose},
+    Purpose,
+};
+use crate::paserk;
+
+pub struct Local;
+impl Local {
+    pub const NAME: &'static str = "local";
+}
+
+impl Purpose for Local {
+    const NAME: &'static str = Local::NAME;
+}
+
+impl PasetoPurpose for Local {
+    type Paseto = pLocal;
+    type Key = Key<32>;
+    type VerifyingKey = Key<32>;
+
+    fn key_id(key: &Key<32>) -> String {
+        paserk::id(
+            paserk::LOCAL_ID,
+            &paserk::encode(paserk::LOCAL, key.as_slice()),
+        )
+    }
+
+    fn build(
+        builder: &mut GenericBuilder<'_, '_, pV4, pLocal>,
+        key: &Key<32>,
+    ) -> Result<String, GenericBuilderError> {
+        builder.try_encrypt(&PasetoSymmetricKey::<pV4, pLocal>::from(key.clone()))
+    }
+
+    fn parse(
+        token: &str,
+        key: &Key<32>,
+        footer: Option<&str>,
+        implicit_assertion: Option<&str>,
+    ) -> Result<Value, GenericParserError> {
+        let key = PasetoSymmetricKey::<pV4, pLocal>::from(key.clone());
+        let mut parser = parser::<pLocal>(footer, implicit_assertion);
+        parser.parse(token, &key)
+    }
+}
@@ -1,3 +1,61 @@
-pub trait Purpose {}
+pub trait Purpose: sealed::PasetoPurpose {
+    /// The purpose part of a token header, e.g. `public`.
+    const NAME: &'static str;
+}
+mod local;
 mod public;
+pub use local::Local;
 pub use public::Public;
+
+pub(crate) mod sealed {
+    use rusty_paseto::{
+        core::{Footer, ImplicitAssertion, V4},
+        prelude::{GenericBuilder, GenericBuilderError, GenericParserError, PasetoParser},
+    };
+    use serde_json::Value;
+
+    /// Supplies the keys and `rusty_paseto` calls that differ between purposes, so a [`Maker`](crate::Maker)
+    /// builds and parses the tokens of every purpose the same way.
+    pub trait PasetoPurpose {
+        /// The `rusty_paseto` purpose of the tokens.
+        type Paseto;
+
+        /// The key tokens are created with.
+        type Key;
+
+        /// The key tokens are verified or decrypted with.
+        type VerifyingKey: Clone;
+
+        /// Returns the PASERK ID of `key`, i.e. the `kid` embedded in token footers.
+        fn key_id(key: &Self::VerifyingKey) -> String;
+
+        /// Signs or encrypts the claims of `builder` with `key`.
+        fn build(
+            builder: &mut GenericBuilder<'_, '_, V4, Self::Paseto>,
+            key: &Self::Key,
+        ) -> Result<String, GenericBuilderError>;
+
+        /// Verifies or decrypts `token` with `key` and returns its claims.
+        fn parse(
+            token: &str,
+            key: &Self::VerifyingKey,
+            footer: Option<&str>,
+            implicit_assertion: Option<&str>,
+        ) -> Result<Value, GenericParserError>;
+    }
+
+    /// Returns a parser that expects the given footer and implicit assertion.
+    pub(crate) fn parser<'a, P>(
+        footer: Option<&'a str>,
+        implicit_assertion: Option<&'a str>,
+    ) -> PasetoParser<'a, V4, P> {
+        let mut parser = PasetoParser::<V4, P>::new();
+        if let Some(footer) = footer {
+            parser.set_footer(Footer::from(footer));
+        }
+        if let Some(implicit_assertion) = implicit_assertion {
+            parser.set_implicit_assertion(ImplicitAssertion::from(implicit_assertion));
+        }
+        parser
+    }
+}
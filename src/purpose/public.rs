@@ -1,8 +1,53 @@
-use super::Purpose;
+use rusty_paseto::{
+    core::{
+        Key, PasetoAsymmetricPrivateKey, PasetoAsymmetricPublicKey, Public as pPublic, V4 as pV4,
+    },
+    prelude::{GenericBuilder, GenericBuilderError, GenericParserError},
+};
+use serde_json::Value;
+
+use super::{
+    sealed::{parser, PasetoPurpose},
+    Purpose,
+};
+use crate::paserk;
 
 pub struct Public;
 impl Public {
     pub const NAME: &'static str = "public";
 }
 
-impl Purpose for Public {}
+impl Purpose for Public {
+    const NAME: &'static str = Public::NAME;
+}
+
+impl PasetoPurpose for Public {
+    type Paseto = pPublic;
+    type Key = Key<64>;
+    type VerifyingKey = [u8; 32];
+
+    fn key_id(key: &[u8; 32]) -> String {
+        paserk::id(paserk::PUBLIC_ID, &paserk::encode(paserk::PUBLIC, key))
+    }
+
+    fn build(
+        builder: &mut GenericBuilder<'_, '_, pV4, pPublic>,
+        key: &Key<64>,
+    ) -> Result<String, GenericBuilderError> {
+        builder.try_sign(&PasetoAsymmetricPrivateKey::<pV4, pPublic>::from(
+            key.as_slice(),
+        ))
+    }
+
+    fn parse(
+        token: &str,
+        key: &[u8; 32],
+        footer: Option<&str>,
+        implicit_assertion: Option<&str>,
+    ) -> Result<Value, GenericParserError> {
+        let key = Key::<32>::from(key);
+        let key = PasetoAsymmetricPublicKey::<pV4, pPublic>::from(&key);
+        let mut parser = parser::<pPublic>(footer, implicit_assertion);
+        parser.parse(token, &key)
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{
+    claims::reserved,
+    errors::{StoreError, TokenError, ValidationError},
+    Claims,
+};
+
+/// Records consumed one-time tokens by their `jti` claim until they expire.
+///
+/// Used by `Maker::consume_token` for tokens that must be accepted exactly once, such as password reset or email
+/// confirmation links. A token is kept until its `exp` plus the verification leeway, as long as it would still be
+/// accepted. Implementations for shared backends must check and record a `jti` atomically, e.g. with
+/// Redis `SET NX` and an expiry of `expires_at - now`.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, MemoryReplayStore, errors::TokenError};
+///
+/// let store = MemoryReplayStore::new();
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker");
+/// let token = maker.create_token(&Claims::new().with_token_identifier("reset-1")).unwrap();
+///
+/// assert!(maker.consume_token(&token, &store).is_ok());
+/// assert!(matches!(maker.consume_token(&token, &store), Err(TokenError::Replayed)));
+/// ```
+pub trait ReplayStore: Debug + Send + Sync {
+    /// Marks `token_identifier` as consumed until `expires_at`. Returns `false` if it was already consumed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the store cannot be read or written.
+    fn consume(
+        &self,
+        token_identifier: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StoreError>;
+}
+
+impl<S: ReplayStore + ?Sized> ReplayStore for Arc<S> {
+    fn consume(
+        &self,
+        token_identifier: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StoreError> {
+        (**self).consume(token_identifier, expires_at, now)
+    }
+}
+
+/// A [`ReplayStore`] kept in memory. Expired entries are evicted whenever a token is consumed.
+#[derive(Debug, Default)]
+pub struct MemoryReplayStore {
+    consumed: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl MemoryReplayStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of consumed tokens that have not been evicted yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.consumed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ReplayStore for MemoryReplayStore {
+    fn consume(
+        &self,
+        token_identifier: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StoreError> {
+        let mut consumed = self.consumed.lock().unwrap_or_else(PoisonError::into_inner);
+        consumed.retain(|_, expires_at| *expires_at > now);
+        if consumed.contains_key(token_identifier) {
+            return Ok(false);
+        }
+        consumed.insert(token_identifier.to_string(), expires_at);
+        Ok(true)
+    }
+}
+
+/// Records verified claims as consumed until their `exp` plus `leeway`, rejecting them if their `jti` was consumed
+/// before.
+pub(crate) fn consume(
+    store: &dyn ReplayStore,
+    claims: Claims,
+    leeway: Duration,
+    now: DateTime<Utc>,
+) -> Result<Claims, TokenError> {
+    let token_identifier = claims
+        .get_token_identifier()
+        .ok_or_else(|| ValidationError::MissingClaim(reserved::TOKEN_IDENTIFIER.to_string()))?;
+    let expires_at = claims
+        .get_expiration()
+        .ok_or_else(|| ValidationError::MissingClaim(reserved::EXPIRATION.to_string()))?;
+    if store.consume(&token_identifier, expires_at + leeway, now)? {
+        Ok(claims)
+    } else {
+        Err(TokenError::Replayed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Clock, FixedClock, Maker};
+
+    #[test]
+    fn test_eviction() {
+        let store = MemoryReplayStore::new();
+        let now = Utc::now();
+
+        assert!(store.consume("a", now + Duration::minutes(5), now).unwrap());
+        assert!(store
+            .consume("b", now + Duration::minutes(10), now)
+            .unwrap());
+        assert!(!store.consume("a", now + Duration::minutes(5), now).unwrap());
+        assert_eq!(store.len(), 2);
+
+        let later = now + Duration::minutes(6);
+        assert!(store
+            .consume("c", later + Duration::minutes(5), later)
+            .unwrap());
+        assert_eq!(store.len(), 2);
+        assert!(store
+            .consume("a", later + Duration::minutes(5), later)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_consume_requires_claims() {
+        let store = MemoryReplayStore::new();
+        let now = Utc::now();
+
+        assert!(matches!(
+            consume(&store, Claims::new().with_expiration_time(now), Duration::zero(), now),
+            Err(TokenError::Validation(ValidationError::MissingClaim(key))) if key == "jti"
+        ));
+        assert!(matches!(
+            consume(&store, Claims::new().with_token_identifier("a"), Duration::zero(), now),
+            Err(TokenError::Validation(ValidationError::MissingClaim(key))) if key == "exp"
+        ));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_consume_within_leeway() {
+        let clock = FixedClock::new(Utc::now());
+        let store = MemoryReplayStore::new();
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key)
+            .expect("failed to create maker")
+            .with_clock(clock.clone())
+            .with_leeway(Duration::seconds(60));
+        let token = maker
+            .create_token(
+                &Claims::new()
+                    .with_token_identifier("reset-1")
+                    .with_expiration_time(clock.now() + Duration::minutes(5)),
+            )
+            .expect("failed to generate token");
+
+        clock.advance(Duration::minutes(5) + Duration::seconds(10));
+        assert!(maker.consume_token(&token, &store).is_ok());
+        clock.advance(Duration::seconds(1));
+        assert!(matches!(
+            maker.consume_token(&token, &store),
+            Err(TokenError::Replayed)
+        ));
+        assert!(matches!(
+            maker.verifier().consume_token(&token, &store),
+            Err(TokenError::Replayed)
+        ));
+    }
+}
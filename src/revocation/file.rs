@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+use super::{Revocation, RevocationList, RevocationStore};
+use crate::errors::StoreError;
+
+/// A [`RevocationStore`] persisted as an append-only log of JSON lines.
+///
+/// The log is replayed into memory when the store is opened, so lookups never touch the disk. Every
+/// revocation is appended and flushed to the file before it takes effect.
+#[derive(Debug)]
+pub struct FileRevocationStore {
+    state: Mutex<(File, RevocationList)>,
+}
+
+impl FileRevocationStore {
+    /// Opens the revocation log at `path`, creating it if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be opened or contains an invalid entry.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut list = RevocationList::default();
+        for (number, line) in BufReader::new(&mut file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let revocation = serde_json::from_str(&line)
+                .map_err(|err| StoreError::Corrupt(format!("line {}: {err}", number + 1)))?;
+            list.apply(revocation);
+        }
+        Ok(Self {
+            state: Mutex::new((file, list)),
+        })
+    }
+
+    fn append(&self, revocation: Revocation) -> Result<(), StoreError> {
+        let line = serde_json::to_string(&revocation)
+            .map_err(|err| StoreError::Corrupt(err.to_string()))?;
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let (file, list) = &mut *state;
+        writeln!(file, "{line}")?;
+        file.sync_data()?;
+        list.apply(revocation);
+        Ok(())
+    }
+}
+
+impl RevocationStore for FileRevocationStore {
+    fn revoke_token(&self, token_identifier: &str) -> Result<(), StoreError> {
+        self.append(Revocation::Token {
+            jti: token_identifier.to_string(),
+        })
+    }
+
+    fn revoke_subject(&self, subject: &str, before: DateTime<Utc>) -> Result<(), StoreError> {
+        self.append(Revocation::Subject {
+            sub: subject.to_string(),
+            before,
+        })
+    }
+
+    fn is_token_revoked(&self, token_identifier: &str) -> Result<bool, StoreError> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(state.1.tokens.contains(token_identifier))
+    }
+
+    fn subject_revoked_before(&self, subject: &str) -> Result<Option<DateTime<Utc>>, StoreError> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(state.1.subjects.get(subject).copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_persistence() {
+        let path = std::env::temp_dir().join(format!("revocations-{}.jsonl", uuid::Uuid::new_v4()));
+        let before = Utc::now();
+        {
+            let store = FileRevocationStore::open(&path).unwrap();
+            store.revoke_token("token_id").unwrap();
+            store.revoke_subject("user", before).unwrap();
+        }
+
+        let store = FileRevocationStore::open(&path).unwrap();
+        assert!(store.is_token_revoked("token_id").unwrap());
+        assert!(!store.is_token_revoked("other").unwrap());
+        assert_eq!(store.subject_revoked_before("user").unwrap(), Some(before));
+        assert_eq!(store.subject_revoked_before("other").unwrap(), None);
+
+        fs::write(&path, "not json\n").unwrap();
+        assert!(matches!(
+            FileRevocationStore::open(&path),
+            Err(StoreError::Corrupt(_))
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use super::{Revocation, RevocationList, RevocationStore};
+use crate::errors::StoreError;
+
+/// A [`RevocationStore`] kept in memory. Revocations are lost when the process exits.
+#[derive(Debug, Default)]
+pub struct MemoryRevocationStore {
+    list: Mutex<RevocationList>,
+}
+
+impl MemoryRevocationStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn list(&self) -> MutexGuard<'_, RevocationList> {
+        self.list.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RevocationStore for MemoryRevocationStore {
+    fn revoke_token(&self, token_identifier: &str) -> Result<(), StoreError> {
+        self.list().apply(Revocation::Token {
+            jti: token_identifier.to_string(),
+        });
+        Ok(())
+    }
+
+    fn revoke_subject(&self, subject: &str, before: DateTime<Utc>) -> Result<(), StoreError> {
+        self.list().apply(Revocation::Subject {
+            sub: subject.to_string(),
+            before,
+        });
+        Ok(())
+    }
+
+    fn is_token_revoked(&self, token_identifier: &str) -> Result<bool, StoreError> {
+        Ok(self.list().tokens.contains(token_identifier))
+    }
+
+    fn subject_revoked_before(&self, subject: &str) -> Result<Option<DateTime<Utc>>, StoreError> {
+        Ok(self.list().subjects.get(subject).copied())
+    }
+}
@@ -0,0 +1,209 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+};
+
+use crate::{
+    errors::{StoreError, TokenError},
+    Claims,
+};
+
+mod file;
+mod memory;
+
+pub use file::FileRevocationStore;
+pub use memory::MemoryRevocationStore;
+
+/// Records revoked tokens so they are rejected before they expire.
+///
+/// Tokens are revoked individually by their `jti` claim, or for a whole subject by revoking every token whose
+/// `iat` claim lies before a cutoff, e.g. on logout or password change. Implementations use interior
+/// mutability so a store can be shared between a `Maker` and the code that revokes tokens.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, MemoryRevocationStore, RevocationStore, errors::TokenError};
+/// use std::sync::Arc;
+///
+/// let store = Arc::new(MemoryRevocationStore::new());
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key)
+///     .expect("failed to create maker")
+///     .with_revocation_store(Arc::clone(&store));
+///
+/// let token = maker.create_token(&Claims::new().with_token_identifier("token_id")).unwrap();
+/// assert!(maker.verify_token(&token).is_ok());
+///
+/// store.revoke_token("token_id").unwrap();
+/// assert!(matches!(maker.verify_token(&token), Err(TokenError::Revoked)));
+/// ```
+pub trait RevocationStore: Debug + Send + Sync {
+    /// # Errors
+    ///
+    /// This function will return an error if the revocation cannot be stored.
+    fn revoke_token(&self, token_identifier: &str) -> Result<(), StoreError>;
+
+    /// Revokes every token of `subject` issued before `before`, including tokens issued within the same second.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the revocation cannot be stored.
+    fn revoke_subject(&self, subject: &str, before: DateTime<Utc>) -> Result<(), StoreError>;
+
+    /// # Errors
+    ///
+    /// This function will return an error if the store cannot be read.
+    fn is_token_revoked(&self, token_identifier: &str) -> Result<bool, StoreError>;
+
+    /// Returns the cutoff before which tokens of `subject` are revoked, if any.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the store cannot be read.
+    fn subject_revoked_before(&self, subject: &str) -> Result<Option<DateTime<Utc>>, StoreError>;
+}
+
+impl<S: RevocationStore + ?Sized> RevocationStore for Arc<S> {
+    fn revoke_token(&self, token_identifier: &str) -> Result<(), StoreError> {
+        (**self).revoke_token(token_identifier)
+    }
+
+    fn revoke_subject(&self, subject: &str, before: DateTime<Utc>) -> Result<(), StoreError> {
+        (**self).revoke_subject(subject, before)
+    }
+
+    fn is_token_revoked(&self, token_identifier: &str) -> Result<bool, StoreError> {
+        (**self).is_token_revoked(token_identifier)
+    }
+
+    fn subject_revoked_before(&self, subject: &str) -> Result<Option<DateTime<Utc>>, StoreError> {
+        (**self).subject_revoked_before(subject)
+    }
+}
+
+/// Rejects verified claims that were revoked by `jti`, or by `sub` with an `iat` before the subject's cutoff.
+///
+/// The cutoff and `iat` are both compared at one-second precision and fail closed: a token issued in the same second
+/// as the cutoff is revoked, whether it was issued before or after it. Tokens of a revoked subject without an `iat`
+/// claim are rejected.
+pub(crate) fn check_revoked(
+    store: &dyn RevocationStore,
+    claims: &Claims,
+) -> Result<(), TokenError> {
+    if let Some(token_identifier) = claims.get_token_identifier() {
+        if store.is_token_revoked(&token_identifier)? {
+            return Err(TokenError::Revoked);
+        }
+    }
+    if let Some(subject) = claims.get_subject() {
+        if let Some(before) = store.subject_revoked_before(&subject)? {
+            let before = before.trunc_subsecs(0);
+            if claims
+                .get_issued_at()
+                .is_none_or(|issued_at| issued_at.trunc_subsecs(0) <= before)
+            {
+                return Err(TokenError::Revoked);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A single revocation, as kept in memory and written to the log of a [`FileRevocationStore`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Revocation {
+    Token { jti: String },
+    Subject { sub: String, before: DateTime<Utc> },
+}
+
+#[derive(Debug, Default)]
+struct RevocationList {
+    tokens: HashSet<String>,
+    subjects: HashMap<String, DateTime<Utc>>,
+}
+
+impl RevocationList {
+    fn apply(&mut self, revocation: Revocation) {
+        match revocation {
+            Revocation::Token { jti } => {
+                self.tokens.insert(jti);
+            }
+            Revocation::Subject { sub, before } => {
+                let cutoff = self.subjects.entry(sub).or_insert(before);
+                *cutoff = (*cutoff).max(before);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_check_revoked() {
+        let store = MemoryRevocationStore::new();
+        let now = Utc::now();
+        let claims = Claims::new()
+            .with_token_identifier("token_id")
+            .with_subject("user")
+            .with_issued_at_time(now);
+        assert!(check_revoked(&store, &claims).is_ok());
+
+        store
+            .revoke_subject("user", now - Duration::minutes(1))
+            .unwrap();
+        assert!(check_revoked(&store, &claims).is_ok());
+        assert!(matches!(
+            check_revoked(&store, &Claims::new().with_subject("user")),
+            Err(TokenError::Revoked)
+        ));
+
+        store
+            .revoke_subject("user", now + Duration::seconds(1))
+            .unwrap();
+        assert!(matches!(
+            check_revoked(&store, &claims),
+            Err(TokenError::Revoked)
+        ));
+        store
+            .revoke_subject("user", now - Duration::days(1))
+            .unwrap();
+        assert_eq!(
+            store.subject_revoked_before("user").unwrap(),
+            Some(now + Duration::seconds(1))
+        );
+
+        let claims = Claims::new().with_token_identifier("token_id");
+        assert!(check_revoked(&store, &claims).is_ok());
+        store.revoke_token("token_id").unwrap();
+        assert!(matches!(
+            check_revoked(&store, &claims),
+            Err(TokenError::Revoked)
+        ));
+    }
+    #[test]
+    fn test_check_revoked_subsecond() {
+        let store = MemoryRevocationStore::new();
+        let claims = Claims::new()
+            .with_subject("user")
+            .with_issued_at("2024-01-01T00:00:00.500Z")
+            .unwrap();
+        let cutoff = DateTime::parse_from_rfc3339("2024-01-01T00:00:00.800Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        store.revoke_subject("user", cutoff).unwrap();
+        assert!(matches!(
+            check_revoked(&store, &claims),
+            Err(TokenError::Revoked)
+        ));
+    }
+}
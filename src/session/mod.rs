@@ -0,0 +1,519 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{
+    claims::reserved,
+    errors::{StoreError, TokenError, ValidationError},
+    purpose::Public,
+    token::{SignedToken, TokenInput},
+    version::V4,
+    Claims, Maker,
+};
+
+/// The claim of an access or refresh token that names its token family.
+const FAMILY: &str = "fam";
+
+/// Implicit assertions that keep access and refresh tokens from being used in place of each other.
+const ACCESS: &str = "paseto_maker.access";
+const REFRESH: &str = "paseto_maker.refresh";
+
+const DEFAULT_ACCESS_LIFETIME: Duration = Duration::minutes(15);
+const DEFAULT_REFRESH_LIFETIME: Duration = Duration::days(30);
+
+/// The outcome of presenting a refresh token to a [`SessionStore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// The refresh token was the latest of its family and has been replaced.
+    Rotated,
+    /// The refresh token had already been rotated. The family is now revoked.
+    Reused,
+    /// The family is unknown, expired or revoked.
+    Revoked,
+}
+
+/// Tracks the latest refresh token of every token family.
+///
+/// Implementations for shared backends must perform `rotate` atomically, so that two concurrent refreshes with the
+/// same token cannot both succeed.
+pub trait SessionStore: Debug + Send + Sync {
+    /// Starts a family whose latest refresh token is `token_identifier`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the family cannot be stored.
+    fn create_family(
+        &self,
+        family: &str,
+        token_identifier: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StoreError>;
+
+    /// Replaces `token_identifier` with `next` as the latest refresh token of `family` if it is the latest one, and
+    /// revokes the family if it is an older one.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the store cannot be read or written.
+    fn rotate(
+        &self,
+        family: &str,
+        token_identifier: &str,
+        next: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Rotation, StoreError>;
+
+    /// # Errors
+    ///
+    /// This function will return an error if the revocation cannot be stored.
+    fn revoke_family(&self, family: &str) -> Result<(), StoreError>;
+
+    /// Returns `false` if `family` is unknown, expired or revoked.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the store cannot be read.
+    fn is_active(&self, family: &str, now: DateTime<Utc>) -> Result<bool, StoreError>;
+}
+
+impl<S: SessionStore + ?Sized> SessionStore for Arc<S> {
+    fn create_family(
+        &self,
+        family: &str,
+        token_identifier: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        (**self).create_family(family, token_identifier, expires_at)
+    }
+
+    fn rotate(
+        &self,
+        family: &str,
+        token_identifier: &str,
+        next: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Rotation, StoreError> {
+        (**self).rotate(family, token_identifier, next, expires_at, now)
+    }
+
+    fn revoke_family(&self, family: &str) -> Result<(), StoreError> {
+        (**self).revoke_family(family)
+    }
+
+    fn is_active(&self, family: &str, now: DateTime<Utc>) -> Result<bool, StoreError> {
+        (**self).is_active(family, now)
+    }
+}
+
+#[derive(Debug)]
+struct Family {
+    latest: String,
+    expires_at: DateTime<Utc>,
+    revoked: bool,
+}
+
+/// A [`SessionStore`] kept in memory. Expired families are evicted whenever a refresh token is rotated.
+#[derive(Debug, Default)]
+pub struct MemorySessionStore {
+    families: Mutex<HashMap<String, Family>>,
+}
+
+impl MemorySessionStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    fn create_family(
+        &self,
+        family: &str,
+        token_identifier: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), StoreError> {
+        let mut families = self.families.lock().unwrap_or_else(PoisonError::into_inner);
+        families.insert(
+            family.to_string(),
+            Family {
+                latest: token_identifier.to_string(),
+                expires_at,
+                revoked: false,
+            },
+        );
+        Ok(())
+    }
+
+    fn rotate(
+        &self,
+        family: &str,
+        token_identifier: &str,
+        next: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Rotation, StoreError> {
+        let mut families = self.families.lock().unwrap_or_else(PoisonError::into_inner);
+        families.retain(|_, family| family.expires_at > now);
+        let Some(family) = families.get_mut(family) else {
+            return Ok(Rotation::Revoked);
+        };
+        if family.revoked {
+            return Ok(Rotation::Revoked);
+        }
+        if family.latest != token_identifier {
+            family.revoked = true;
+            return Ok(Rotation::Reused);
+        }
+        family.latest = next.to_string();
+        family.expires_at = expires_at;
+        Ok(Rotation::Rotated)
+    }
+
+    fn revoke_family(&self, family: &str) -> Result<(), StoreError> {
+        let mut families = self.families.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(family) = families.get_mut(family) {
+            family.revoked = true;
+        }
+        Ok(())
+    }
+
+    fn is_active(&self, family: &str, now: DateTime<Utc>) -> Result<bool, StoreError> {
+        let families = self.families.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(families
+            .get(family)
+            .is_some_and(|family| !family.revoked && family.expires_at > now))
+    }
+}
+
+/// An access token together with the refresh token that renews it.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenPair {
+    pub access_token: SignedToken<V4, Public>,
+    pub access_expires_at: DateTime<Utc>,
+    pub refresh_token: SignedToken<V4, Public>,
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+/// Issues short-lived access tokens with long-lived, rotating refresh tokens.
+///
+/// Every refresh token belongs to a token family that starts with `issue_pair`. `refresh` swaps the latest refresh
+/// token of a family for a new pair. Presenting an older refresh token of the family again means it was stolen or
+/// leaked, so the whole family is revoked and neither party can refresh any longer.
+///
+/// Access and refresh tokens are signed with different implicit assertions, so one cannot be used as the other.
+/// Both carry the family in the `fam` claim, and `verify_access_token` rejects access tokens of a revoked family
+/// before they expire. Every access token verification therefore reads the store.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, MemorySessionStore, SessionManager, errors::TokenError};
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker");
+/// let sessions = SessionManager::new(maker, MemorySessionStore::new());
+///
+/// let pair = sessions.issue_pair(&Claims::new().with_subject("user")).unwrap();
+/// let claims = sessions.verify_access_token(&pair.access_token).unwrap();
+/// assert_eq!(claims.get_subject(), Some("user".to_string()));
+///
+/// let refreshed = sessions.refresh(&pair.refresh_token).unwrap();
+/// assert!(sessions.verify_access_token(&refreshed.access_token).is_ok());
+///
+/// // The old refresh token was rotated, so using it again revokes the family.
+/// assert!(matches!(sessions.refresh(&pair.refresh_token), Err(TokenError::RefreshTokenReused)));
+/// assert!(matches!(sessions.refresh(&refreshed.refresh_token), Err(TokenError::Revoked)));
+/// assert!(matches!(sessions.verify_access_token(&refreshed.access_token), Err(TokenError::Revoked)));
+/// ```
+///
+/// # Methods
+///
+/// - `new`: Creates a `SessionManager` that signs with `maker` and tracks families in `store`.
+/// - `with_access_lifetime`: Sets the lifetime of access tokens, 15 minutes by default.
+/// - `with_refresh_lifetime`: Sets the lifetime of refresh tokens, 30 days by default.
+/// - `issue_pair`: Starts a token family and returns its first pair.
+/// - `refresh`: Swaps the latest refresh token of a family for a new pair.
+/// - `verify_access_token`: Verifies an access token and checks that its family is not revoked.
+/// - `revoke`: Revokes the family of a refresh token, e.g. on logout.
+pub struct SessionManager {
+    maker: Maker<V4, Public>,
+    store: Arc<dyn SessionStore>,
+    access_lifetime: Duration,
+    refresh_lifetime: Duration,
+}
+
+impl SessionManager {
+    #[must_use]
+    pub fn new<S: SessionStore + 'static>(maker: Maker<V4, Public>, store: S) -> Self {
+        Self {
+            maker,
+            store: Arc::new(store),
+            access_lifetime: DEFAULT_ACCESS_LIFETIME,
+            refresh_lifetime: DEFAULT_REFRESH_LIFETIME,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_access_lifetime(mut self, lifetime: Duration) -> Self {
+        self.access_lifetime = lifetime;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_refresh_lifetime(mut self, lifetime: Duration) -> Self {
+        self.refresh_lifetime = lifetime;
+        self
+    }
+
+    /// Starts a new token family for `claims` and returns its first token pair.
+    ///
+    /// The `exp`, `iat` and `jti` claims are set by the session manager, and an `nbf` claim is left out.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token creation fails or the family cannot be stored.
+    pub fn issue_pair(&self, claims: &Claims) -> Result<TokenPair, TokenError> {
+        let claims = session_claims(claims);
+        let family = uuid::Uuid::new_v4().to_string();
+        let token_identifier = uuid::Uuid::new_v4().to_string();
+        let now = self.maker.now();
+        self.store
+            .create_family(&family, &token_identifier, now + self.refresh_lifetime)?;
+        self.create_pair(&claims, &family, &token_identifier, now)
+    }
+
+    /// Swaps a refresh token for a new token pair with the same claims, retiring the presented refresh token.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the refresh token is invalid or its family was revoked. If the refresh
+    /// token had already been rotated, its family is revoked and the error is [`TokenError::RefreshTokenReused`].
+    pub fn refresh(
+        &self,
+        refresh_token: impl TokenInput<V4, Public>,
+    ) -> Result<TokenPair, TokenError> {
+        let (claims, family, token_identifier) = self.verify_refresh_token(refresh_token)?;
+        let next = uuid::Uuid::new_v4().to_string();
+        let now = self.maker.now();
+        match self.store.rotate(
+            &family,
+            &token_identifier,
+            &next,
+            now + self.refresh_lifetime,
+            now,
+        )? {
+            Rotation::Rotated => self.create_pair(&session_claims(&claims), &family, &next, now),
+            Rotation::Reused => Err(TokenError::RefreshTokenReused),
+            Rotation::Revoked => Err(TokenError::Revoked),
+        }
+    }
+
+    /// Verifies an access token and checks that its token family is neither revoked nor expired.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the token verification fails, the token is not an access token or its
+    /// family was revoked.
+    pub fn verify_access_token(
+        &self,
+        token: impl TokenInput<V4, Public>,
+    ) -> Result<Claims, TokenError> {
+        let claims = self.maker.verify_token_with_assertion(token, ACCESS)?;
+        let family = claims
+            .get_claim::<String>(FAMILY)
+            .ok_or_else(|| ValidationError::MissingClaim(FAMILY.to_string()))?;
+        if self.store.is_active(&family, self.maker.now())? {
+            Ok(claims)
+        } else {
+            Err(TokenError::Revoked)
+        }
+    }
+
+    /// Revokes the token family of a refresh token, so none of its refresh tokens can be used any longer.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the refresh token is invalid or the revocation cannot be stored.
+    pub fn revoke(&self, refresh_token: impl TokenInput<V4, Public>) -> Result<(), TokenError> {
+        let (_, family, _) = self.verify_refresh_token(refresh_token)?;
+        Ok(self.store.revoke_family(&family)?)
+    }
+
+    fn verify_refresh_token(
+        &self,
+        token: impl TokenInput<V4, Public>,
+    ) -> Result<(Claims, String, String), TokenError> {
+        let claims = self.maker.verify_token_with_assertion(token, REFRESH)?;
+        let family = claims
+            .get_claim::<String>(FAMILY)
+            .ok_or_else(|| ValidationError::MissingClaim(FAMILY.to_string()))?;
+        let token_identifier = claims
+            .get_token_identifier()
+            .ok_or_else(|| ValidationError::MissingClaim(reserved::TOKEN_IDENTIFIER.to_string()))?;
+        Ok((claims, family, token_identifier))
+    }
+
+    fn create_pair(
+        &self,
+        claims: &Claims,
+        family: &str,
+        token_identifier: &str,
+        now: DateTime<Utc>,
+    ) -> Result<TokenPair, TokenError> {
+        let mut access = claims
+            .clone()
+            .with_expiration_time(now + self.access_lifetime);
+        access.set_claim(FAMILY, family)?;
+
+        let mut refresh = claims
+            .clone()
+            .with_expiration_time(now + self.refresh_lifetime)
+            .with_token_identifier(token_identifier);
+        refresh.set_claim(FAMILY, family)?;
+
+        Ok(TokenPair {
+            access_token: self.maker.create_token_with_assertion(&access, ACCESS)?,
+            access_expires_at: expiration(&access)?,
+            refresh_token: self.maker.create_token_with_assertion(&refresh, REFRESH)?,
+            refresh_expires_at: expiration(&refresh)?,
+        })
+    }
+}
+
+/// Returns the `exp` claim as signed into a token, i.e. truncated to whole seconds.
+fn expiration(claims: &Claims) -> Result<DateTime<Utc>, TokenError> {
+    Ok(claims
+        .get_expiration()
+        .ok_or_else(|| ValidationError::MissingClaim(reserved::EXPIRATION.to_string()))?)
+}
+
+/// Returns the caller's claims without the claims the session manager sets on every token.
+fn session_claims(claims: &Claims) -> Claims {
+    let mut claims = claims.clone();
+    for key in [
+        reserved::EXPIRATION,
+        reserved::NOT_BEFORE,
+        reserved::ISSUED_AT,
+        reserved::TOKEN_IDENTIFIER,
+        FAMILY,
+    ] {
+        claims.remove_claim(key);
+    }
+    claims
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::SubsecRound;
+
+    use super::*;
+    use crate::{Clock, FixedClock};
+
+    fn sessions(clock: &FixedClock) -> SessionManager {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key)
+            .expect("failed to create maker")
+            .with_clock(clock.clone());
+        SessionManager::new(maker, MemorySessionStore::new())
+            .with_access_lifetime(Duration::minutes(5))
+            .with_refresh_lifetime(Duration::days(1))
+    }
+
+    #[test]
+    fn test_rotation() {
+        let clock = FixedClock::new(Utc::now());
+        let sessions = sessions(&clock);
+        let claims = Claims::new()
+            .with_subject("user")
+            .with_token_identifier("ignored");
+
+        let first = sessions.issue_pair(&claims).unwrap();
+        clock.advance(Duration::minutes(10));
+        assert!(matches!(
+            sessions.verify_access_token(&first.access_token),
+            Err(TokenError::Expired)
+        ));
+
+        let second = sessions.refresh(&first.refresh_token).unwrap();
+        let claims = sessions.verify_access_token(&second.access_token).unwrap();
+        assert_eq!(claims.get_subject(), Some("user".to_string()));
+        let (_, family, _) = sessions
+            .verify_refresh_token(&second.refresh_token)
+            .unwrap();
+        assert_eq!(claims.get_claim::<String>(FAMILY), Some(family));
+        assert_eq!(Some(second.access_expires_at), claims.get_expiration());
+        assert_eq!(
+            second.refresh_expires_at,
+            (clock.now() + Duration::days(1)).trunc_subsecs(0)
+        );
+
+        let third = sessions.refresh(&second.refresh_token).unwrap();
+        assert!(sessions.verify_access_token(&third.access_token).is_ok());
+    }
+
+    #[test]
+    fn test_reuse_revokes_family() {
+        let clock = FixedClock::new(Utc::now());
+        let sessions = sessions(&clock);
+
+        let first = sessions.issue_pair(&Claims::new()).unwrap();
+        let other = sessions.issue_pair(&Claims::new()).unwrap();
+        let second = sessions.refresh(&first.refresh_token).unwrap();
+
+        assert!(matches!(
+            sessions.refresh(&first.refresh_token),
+            Err(TokenError::RefreshTokenReused)
+        ));
+        assert!(matches!(
+            sessions.refresh(&second.refresh_token),
+            Err(TokenError::Revoked)
+        ));
+        assert!(matches!(
+            sessions.verify_access_token(&second.access_token),
+            Err(TokenError::Revoked)
+        ));
+        assert!(sessions.refresh(&other.refresh_token).is_ok());
+        assert!(sessions.verify_access_token(&other.access_token).is_ok());
+    }
+
+    #[test]
+    fn test_token_types() {
+        let clock = FixedClock::new(Utc::now());
+        let sessions = sessions(&clock);
+        let pair = sessions.issue_pair(&Claims::new()).unwrap();
+
+        assert!(sessions.verify_access_token(&pair.refresh_token).is_err());
+        assert!(sessions.refresh(&pair.access_token).is_err());
+    }
+
+    #[test]
+    fn test_revoke_and_expiry() {
+        let clock = FixedClock::new(Utc::now());
+        let sessions = sessions(&clock);
+
+        let pair = sessions.issue_pair(&Claims::new()).unwrap();
+        assert!(sessions.verify_access_token(&pair.access_token).is_ok());
+        sessions.revoke(&pair.refresh_token).unwrap();
+        assert!(matches!(
+            sessions.refresh(&pair.refresh_token),
+            Err(TokenError::Revoked)
+        ));
+        assert!(matches!(
+            sessions.verify_access_token(&pair.access_token),
+            Err(TokenError::Revoked)
+        ));
+
+        let pair = sessions.issue_pair(&Claims::new()).unwrap();
+        clock.advance(Duration::days(2));
+        assert!(matches!(
+            sessions.refresh(&pair.refresh_token),
+            Err(TokenError::Expired)
+        ));
+    }
+}
@@ -0,0 +1,224 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rusty_paseto::{core::PasetoError, prelude::GenericParserError};
+use std::str::FromStr;
+
+use crate::{
+    errors::TokenError,
+    footer::{Footer, KEY_ID},
+    purpose::{Public, Purpose},
+    version::{Version, V4},
+    Claims,
+};
+
+mod signed;
+
+pub use signed::{SignedToken, TokenInput};
+
+const SIGNATURE_LENGTH: usize = 64;
+
+/// A `V4.public` token split into its parts, without verifying its signature.
+///
+/// Useful to read the footer `kid` before choosing a key, or to look at a token in debugging tools and log
+/// pipelines that hold no key at all. Nothing read from a `Token` is authenticated; verify the token with a
+/// [`Maker`](crate::Maker), [`Verifier`](crate::Verifier) or [`KeyRing`](crate::KeyRing) before trusting it.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, Token};
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker").with_key_id_footer();
+/// let token = maker.create_token(&Claims::new().with_subject("user")).unwrap();
+///
+/// let parsed = Token::parse(&token).unwrap();
+/// assert_eq!(parsed.key_id(), Some(maker.key_id()));
+///
+/// let (claims, _footer) = parsed.inspect_unverified().unwrap();
+/// assert_eq!(claims.get_subject(), Some("user".to_string()));
+/// ```
+///
+/// # Methods
+///
+/// - `parse`: Splits a token string into its parts, rejecting anything but `V4.public` tokens.
+/// - `version`, `purpose`: Return the parts of the token header, i.e. `v4` and `public`.
+/// - `payload`: Returns the signed message, i.e. the JSON encoded claims.
+/// - `signature`: Returns the Ed25519 signature over the header, payload and footer.
+/// - `footer`: Returns the decoded footer, if any.
+/// - `key_id`: Returns the `kid` field of a JSON footer, if any.
+/// - `inspect_unverified`: Decodes the claims and footer without verifying the signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    payload: Vec<u8>,
+    signature: [u8; SIGNATURE_LENGTH],
+    footer: Option<String>,
+}
+
+impl Token {
+    /// # Errors
+    ///
+    /// This function will return an error if the token is not a `V4.public` token or its parts cannot be decoded.
+    pub fn parse(token: impl AsRef<str>) -> Result<Self, TokenError> {
+        let (mut payload, footer) = split::<V4, Public>(token.as_ref())?;
+        let Some(message_length) = payload.len().checked_sub(SIGNATURE_LENGTH) else {
+            return Err(parser_error(PasetoError::IncorrectSize));
+        };
+        let signature = payload
+            .split_off(message_length)
+            .try_into()
+            .map_err(|_| parser_error(PasetoError::IncorrectSize))?;
+
+        Ok(Self {
+            payload,
+            signature,
+            footer,
+        })
+    }
+
+    #[must_use]
+    pub fn version(&self) -> &'static str {
+        V4::NAME
+    }
+
+    #[must_use]
+    pub fn purpose(&self) -> &'static str {
+        Public::NAME
+    }
+
+    #[must_use]
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    #[must_use]
+    pub fn signature(&self) -> &[u8; SIGNATURE_LENGTH] {
+        &self.signature
+    }
+
+    #[must_use]
+    pub fn footer(&self) -> Option<&str> {
+        self.footer.as_deref()
+    }
+
+    /// Returns the `kid` field of a JSON footer. The key id is **not verified** and only tells which key to
+    /// try; the token must still be verified with it.
+    #[must_use]
+    pub fn key_id(&self) -> Option<String> {
+        Footer::parse(self.footer.as_deref()?).get_claim(KEY_ID)
+    }
+
+    /// Decodes the claims and footer of the token **without verifying its signature**.
+    ///
+    /// The result is untrusted: anyone can create a token with arbitrary claims. Never use it for
+    /// authorization, and do not rely on it having expired or not.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the payload is not a valid claims object.
+    pub fn inspect_unverified(&self) -> Result<(Claims, Option<Footer>), TokenError> {
+        let claims = serde_json::from_slice(&self.payload)
+            .map_err(|err| TokenError::Format(Box::new(err)))?;
+        Ok((claims, self.footer.as_deref().map(Footer::parse)))
+    }
+}
+
+impl FromStr for Token {
+    type Err = TokenError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Self::parse(token)
+    }
+}
+
+/// Splits a token of version `V` and purpose `P` into its decoded payload and footer, rejecting any other header.
+fn split<V: Version, P: Purpose>(token: &str) -> Result<(Vec<u8>, Option<String>), TokenError> {
+    let mut parts = token.split('.');
+    let (Some(version), Some(purpose), Some(payload)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(parser_error(PasetoError::IncorrectSize));
+    };
+    if version != V::NAME || purpose != P::NAME {
+        return Err(parser_error(PasetoError::WrongHeader));
+    }
+    let footer = parts.next().map(decode).transpose()?;
+    if parts.next().is_some() {
+        return Err(parser_error(PasetoError::IncorrectSize));
+    }
+    let footer = footer
+        .map(String::from_utf8)
+        .transpose()
+        .map_err(|err| TokenError::Format(Box::new(err)))?;
+    Ok((decode(payload)?, footer))
+}
+
+fn decode(part: &str) -> Result<Vec<u8>, TokenError> {
+    URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|err| TokenError::Format(Box::new(err)))
+}
+
+/// Maps a malformed token to the same error the parser of a `Maker` returns for it.
+fn parser_error(err: PasetoError) -> TokenError {
+    GenericParserError::from(err).into()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::Maker;
+
+    #[test]
+    fn test_parse() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).unwrap();
+        let claims = Claims::new().with_subject("user");
+        let footer = Footer::json(json!({ "kid": "key-1" })).unwrap();
+        let token = maker.create_token_with_footer(&claims, &footer).unwrap();
+
+        let parsed: Token = token.as_str().parse().unwrap();
+        assert_eq!(parsed.version(), "v4");
+        assert_eq!(parsed.purpose(), "public");
+        assert_eq!(parsed.footer(), Some(r#"{"kid":"key-1"}"#));
+        assert_eq!(parsed.key_id(), Some("key-1".to_string()));
+
+        let (inspected, inspected_footer) = parsed.inspect_unverified().unwrap();
+        assert_eq!(inspected, maker.verify_token(&token).unwrap());
+        assert_eq!(inspected_footer, Some(footer));
+
+        let without_footer = maker.create_token(&claims).unwrap();
+        let parsed = Token::parse(&without_footer).unwrap();
+        assert_eq!(parsed.footer(), None);
+        assert_eq!(parsed.key_id(), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let key = Maker::new_local_key();
+        let local = Maker::new_local(&key).create_token(&Claims::new()).unwrap();
+        assert!(matches!(
+            Token::parse(&local),
+            Err(TokenError::WrongHeader(_))
+        ));
+        assert!(matches!(
+            Token::parse("v4.public"),
+            Err(TokenError::Format(_))
+        ));
+        assert!(matches!(
+            Token::parse("v4.public.AAAA"),
+            Err(TokenError::Format(_))
+        ));
+        assert!(matches!(
+            Token::parse("v4.public.!!!"),
+            Err(TokenError::Format(_))
+        ));
+
+        let payload = URL_SAFE_NO_PAD.encode([b"[]".as_slice(), &[0; 64]].concat());
+        let parsed = Token::parse(format!("v4.public.{payload}")).unwrap();
+        assert!(matches!(
+            parsed.inspect_unverified(),
+            Err(TokenError::Format(_))
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use super::split;
+use crate::{errors::TokenError, footer::Footer, purpose::Purpose, version::Version};
+
+/// A token string of version `V` and purpose `P`, as returned by the `create_token` methods.
+///
+/// The type keeps created tokens apart from other strings, such as unchecked user input. Use `as_str`,
+/// `AsRef<str>` or `Display` to send it. `verify_token` accepts it as well as any `&str`, but only for the same
+/// version and purpose, see [`TokenInput`]. Parsing a string with [`FromStr`] or deserializing one only checks
+/// the header and footer encoding, the token must still be verified.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, Footer, SignedToken, version::V4, purpose::Public};
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker");
+/// let footer = Footer::raw("metadata");
+/// let token = maker.create_token_with_footer(&Claims::new(), &footer).unwrap();
+/// assert_eq!(token.footer(), Some(&footer));
+///
+/// let received: SignedToken<V4, Public> = token.to_string().parse().unwrap();
+/// assert!(maker.verify_token(&received).is_ok());
+/// assert!("v4.local.AAAA".parse::<SignedToken<V4, Public>>().is_err());
+/// ```
+pub struct SignedToken<V, P> {
+    token: String,
+    footer: Option<Footer>,
+    version: PhantomData<V>,
+    purpose: PhantomData<P>,
+}
+
+impl<V, P> SignedToken<V, P> {
+    pub(crate) fn new(token: String, footer: Option<Footer>) -> Self {
+        Self {
+            token,
+            footer,
+            version: PhantomData,
+            purpose: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.token
+    }
+
+    /// Returns the unencrypted footer of the token, if any.
+    #[must_use]
+    pub fn footer(&self) -> Option<&Footer> {
+        self.footer.as_ref()
+    }
+
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.token
+    }
+}
+
+impl<V: Version, P: Purpose> FromStr for SignedToken<V, P> {
+    type Err = TokenError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let (_, footer) = split::<V, P>(token)?;
+        Ok(Self::new(
+            token.to_string(),
+            footer.as_deref().map(Footer::parse),
+        ))
+    }
+}
+
+impl<V, P> Clone for SignedToken<V, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.token.clone(), self.footer.clone())
+    }
+}
+
+impl<V, P> PartialEq for SignedToken<V, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.token == other.token
+    }
+}
+
+impl<V, P> Eq for SignedToken<V, P> {}
+
+impl<V, P> Debug for SignedToken<V, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignedToken")
+            .field("token", &self.token)
+            .field("footer", &self.footer)
+            .finish()
+    }
+}
+
+impl<V, P> Display for SignedToken<V, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.token)
+    }
+}
+
+impl<V, P> AsRef<str> for SignedToken<V, P> {
+    fn as_ref(&self) -> &str {
+        &self.token
+    }
+}
+
+/// A token accepted by the verify methods for tokens of version `V` and purpose `P`: a string, or a
+/// [`SignedToken`] of the same version and purpose.
+///
+/// The trait is sealed. Verifying a [`SignedToken`] of another purpose does not compile:
+///
+/// ```compile_fail
+/// use paseto_maker::{Maker, Claims};
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker");
+/// let local = Maker::new_local(&Maker::new_local_key()).create_token(&Claims::new()).unwrap();
+/// let _ = maker.verify_token(&local);
+/// ```
+pub trait TokenInput<V, P>: sealed::Sealed {
+    /// Returns the token string to verify.
+    fn as_token(&self) -> &str;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for &str {}
+impl sealed::Sealed for String {}
+impl sealed::Sealed for &String {}
+impl<V, P> sealed::Sealed for SignedToken<V, P> {}
+impl<V, P> sealed::Sealed for &SignedToken<V, P> {}
+
+impl<V, P> TokenInput<V, P> for &str {
+    fn as_token(&self) -> &str {
+        self
+    }
+}
+
+impl<V, P> TokenInput<V, P> for String {
+    fn as_token(&self) -> &str {
+        self
+    }
+}
+
+impl<V, P> TokenInput<V, P> for &String {
+    fn as_token(&self) -> &str {
+        self
+    }
+}
+
+impl<V, P> TokenInput<V, P> for SignedToken<V, P> {
+    fn as_token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl<V, P> TokenInput<V, P> for &SignedToken<V, P> {
+    fn as_token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl<V, P> From<SignedToken<V, P>> for String {
+    fn from(token: SignedToken<V, P>) -> Self {
+        token.token
+    }
+}
+
+impl<V, P> Serialize for SignedToken<V, P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.token)
+    }
+}
+
+impl<'de, V: Version, P: Purpose> Deserialize<'de> for SignedToken<V, P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        purpose::{Local, Public},
+        version::V4,
+        Claims, Maker,
+    };
+
+    #[test]
+    fn test_parse() {
+        let (priv_key, _) = Maker::new_keypair();
+        let maker = Maker::new(&priv_key).unwrap().with_key_id_footer();
+        let token = maker.create_token(&Claims::new()).unwrap();
+        assert_eq!(
+            token
+                .footer()
+                .and_then(|footer| footer.get_claim::<String>("kid")),
+            Some(maker.key_id())
+        );
+
+        let parsed: SignedToken<V4, Public> = token.as_str().parse().unwrap();
+        assert_eq!(parsed, token);
+        assert_eq!(parsed.footer(), token.footer());
+
+        assert!(matches!(
+            token.as_str().parse::<SignedToken<V4, Local>>(),
+            Err(TokenError::WrongHeader(_))
+        ));
+        assert!(matches!(
+            "v4.public".parse::<SignedToken<V4, Public>>(),
+            Err(TokenError::Format(_))
+        ));
+        assert!(matches!(
+            "v4.public.!!!".parse::<SignedToken<V4, Public>>(),
+            Err(TokenError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_serde() {
+        let maker = Maker::new_local(&Maker::new_local_key());
+        let token = maker.create_token(&Claims::new()).unwrap();
+
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(json, format!("\"{token}\""));
+        let deserialized: SignedToken<V4, Local> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, token);
+        assert!(serde_json::from_str::<SignedToken<V4, Public>>(&json).is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{
+    claims::reserved,
+    errors::{TokenError, ValidationError},
+    Claims,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Describes the checks a verified token's claims must pass.
+///
+/// The `exp`, `nbf` and `iat` claims are always checked, tolerating the configured leeway. All other
+/// checks are opt-in.
+///
+/// # Examples
+///
+/// ```
+/// use paseto_maker::{Maker, Claims, Validation};
+/// use chrono::Duration;
+///
+/// let (priv_key, _) = Maker::new_keypair();
+/// let maker = Maker::new(&priv_key).expect("failed to create maker");
+/// let claims = Claims::new()
+///     .with_issuer("auth.example.com")
+///     .with_audience("api")
+///     .with_token_identifier("token_id");
+/// let token = maker.create_token(&claims).unwrap();
+///
+/// let validation = Validation::new()
+///     .with_issuer("auth.example.com")
+///     .with_audience("api")
+///     .with_token_identifier_required()
+///     .with_max_age(Duration::minutes(5));
+/// assert!(maker.verify_token_with(&token, &validation).is_ok());
+///
+/// let validation = Validation::new().with_audience("admin");
+/// assert!(maker.verify_token_with(&token, &validation).is_err());
+/// ```
+///
+/// # Methods
+///
+/// - `new`: Creates a `Validation` that only checks the time claims.
+/// - `with_leeway`: Sets the clock-skew leeway for all time-based checks. Defaults to zero, or to the leeway of the
+///   `Maker` or `Verifier` the policy is passed to.
+/// - `with_required_claim`: Requires a claim to be present.
+/// - `with_issuer`: Adds an accepted issuer. Once set, the `iss` claim is required.
+/// - `with_audience`: Adds an accepted audience. Once set, the `aud` claim is required.
+/// - `with_subject`: Adds an accepted subject. Once set, the `sub` claim is required.
+/// - `with_token_identifier_required`: Requires the `jti` claim.
+/// - `with_max_age`: Rejects tokens whose `iat` claim is older than the given duration.
+/// - `validate`: Checks claims against this policy.
+#[derive(Debug, Clone, Default)]
+pub struct Validation {
+    leeway: Option<Duration>,
+    required_claims: BTreeSet<String>,
+    issuers: BTreeSet<String>,
+    audiences: BTreeSet<String>,
+    subjects: BTreeSet<String>,
+    max_age: Option<Duration>,
+}
+
+impl Validation {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub const fn with_leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = Some(leeway);
+        self
+    }
+
+    /// Returns the clock-skew leeway tolerated by the time-based checks.
+    pub(crate) fn leeway(&self) -> Duration {
+        self.leeway.unwrap_or_else(Duration::zero)
+    }
+
+    /// Keeps the leeway of `previous` if this policy does not set its own.
+    pub(crate) const fn inherit_leeway(mut self, previous: &Self) -> Self {
+        if self.leeway.is_none() {
+            self.leeway = previous.leeway;
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn with_required_claim<T: AsRef<str>>(mut self, key: T) -> Self {
+        self.required_claims.insert(key.as_ref().to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_issuer<T: AsRef<str>>(mut self, issuer: T) -> Self {
+        self.issuers.insert(issuer.as_ref().to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_audience<T: AsRef<str>>(mut self, audience: T) -> Self {
+        self.audiences.insert(audience.as_ref().to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_subject<T: AsRef<str>>(mut self, subject: T) -> Self {
+        self.subjects.insert(subject.as_ref().to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_token_identifier_required(self) -> Self {
+        self.with_required_claim(reserved::TOKEN_IDENTIFIER)
+    }
+
+    #[must_use]
+    pub const fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if the claims do not satisfy this policy.
+    pub fn validate(&self, claims: &Claims) -> Result<(), TokenError> {
+        self.validate_at(claims, Utc::now())
+    }
+
+    pub(crate) fn validate_at(
+        &self,
+        claims: &Claims,
+        now: DateTime<Utc>,
+    ) -> Result<(), TokenError> {
+        validate_time_claims(claims, now, self.leeway())?;
+
+        for key in &self.required_claims {
+            if claims.get_claim::<Value>(key).is_none() {
+                return Err(ValidationError::MissingClaim(key.clone()).into());
+            }
+        }
+        check_allowed(claims, reserved::ISSUER, &self.issuers)?;
+        check_allowed(claims, reserved::AUDIENCE, &self.audiences)?;
+        check_allowed(claims, reserved::SUBJECT, &self.subjects)?;
+
+        if let Some(max_age) = self.max_age {
+            let issued_at = time_claim(claims, reserved::ISSUED_AT)?
+                .ok_or_else(|| ValidationError::MissingClaim(reserved::ISSUED_AT.to_string()))?;
+            if now - issued_at > max_age + self.leeway() {
+                return Err(ValidationError::TooOld.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_allowed(
+    claims: &Claims,
+    key: &str,
+    allowed: &BTreeSet<String>,
+) -> Result<(), ValidationError> {
+    if allowed.is_empty() {
+        return Ok(());
+    }
+    match claims.get_claim::<String>(key) {
+        Some(value) if allowed.contains(&value) => Ok(()),
+        Some(value) => Err(ValidationError::UnexpectedValue(key.to_string(), value)),
+        None => Err(ValidationError::MissingClaim(key.to_string())),
+    }
+}
+
+/// Checks the `exp`, `nbf` and `iat` claims against `now`, tolerating up to `leeway` of clock skew.
+fn validate_time_claims(
+    claims: &Claims,
+    now: DateTime<Utc>,
+    leeway: Duration,
+) -> Result<(), TokenError> {
+    if let Some(expiration) = time_claim(claims, reserved::EXPIRATION)? {
+        if now >= expiration + leeway {
+            return Err(TokenError::Expired);
+        }
+    }
+    if let Some(not_before) = time_claim(claims, reserved::NOT_BEFORE)? {
+        if now + leeway < not_before {
+            return Err(TokenError::NotYetValid);
+        }
+    }
+    if let Some(issued_at) = time_claim(claims, reserved::ISSUED_AT)? {
+        if now + leeway < issued_at {
+            return Err(TokenError::IssuedInFuture);
+        }
+    }
+    Ok(())
+}
+
+fn time_claim(claims: &Claims, key: &str) -> Result<Option<DateTime<Utc>>, TokenError> {
+    if claims.get_claim::<Value>(key).is_none() {
+        return Ok(None);
+    }
+    claims
+        .get_claim(key)
+        .map(Some)
+        .ok_or_else(|| TokenError::InvalidClaim(format!("{key} is not a valid RFC 3339 date")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::errors::ClaimError;
+
+    fn claims_at(key: &str, time: DateTime<Utc>) -> Claims {
+        let mut claims = Claims::new();
+        claims.set_claim(key, time.to_rfc3339()).unwrap();
+        claims
+    }
+
+    #[test]
+    fn test_expiration() {
+        let now = Utc::now();
+        let claims = claims_at(reserved::EXPIRATION, now - Duration::seconds(30));
+
+        assert!(matches!(
+            validate_time_claims(&claims, now, Duration::zero()),
+            Err(TokenError::Expired)
+        ));
+        assert!(validate_time_claims(&claims, now, Duration::seconds(60)).is_ok());
+    }
+
+    #[test]
+    fn test_not_before() {
+        let now = Utc::now();
+        let claims = claims_at(reserved::NOT_BEFORE, now + Duration::seconds(30));
+
+        assert!(matches!(
+            validate_time_claims(&claims, now, Duration::zero()),
+            Err(TokenError::NotYetValid)
+        ));
+        assert!(validate_time_claims(&claims, now, Duration::seconds(60)).is_ok());
+    }
+
+    #[test]
+    fn test_issued_at() {
+        let now = Utc::now();
+        let claims = claims_at(reserved::ISSUED_AT, now + Duration::seconds(30));
+
+        assert!(matches!(
+            validate_time_claims(&claims, now, Duration::zero()),
+            Err(TokenError::IssuedInFuture)
+        ));
+        assert!(validate_time_claims(&claims, now, Duration::seconds(60)).is_ok());
+        assert!(validate_time_claims(
+            &claims_at(reserved::ISSUED_AT, now - Duration::days(1)),
+            now,
+            Duration::zero()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_malformed_time_claim() {
+        let claims = Claims::try_from(serde_json::json!({ "exp": "tomorrow" }));
+        assert!(matches!(
+            claims,
+            Err(ClaimError::InvalidReservedClaim(key, _)) if key == "exp"
+        ));
+    }
+
+    #[test]
+    fn test_required_claims() {
+        let validation = Validation::new()
+            .with_required_claim("role")
+            .with_token_identifier_required();
+
+        let mut claims = Claims::new().with_token_identifier("token_id");
+        assert!(matches!(
+            validation.validate(&claims),
+            Err(TokenError::Validation(ValidationError::MissingClaim(key))) if key == "role"
+        ));
+
+        claims.set_claim("role", "admin").unwrap();
+        assert!(validation.validate(&claims).is_ok());
+    }
+
+    #[test]
+    fn test_allow_lists() {
+        let validation = Validation::new()
+            .with_issuer("issuer-a")
+            .with_issuer("issuer-b")
+            .with_audience("api")
+            .with_subject("user");
+
+        let claims = Claims::new()
+            .with_issuer("issuer-b")
+            .with_audience("api")
+            .with_subject("user");
+        assert!(validation.validate(&claims).is_ok());
+
+        let claims = Claims::new()
+            .with_issuer("issuer-c")
+            .with_audience("api")
+            .with_subject("user");
+        assert!(matches!(
+            validation.validate(&claims),
+            Err(TokenError::Validation(ValidationError::UnexpectedValue(key, value)))
+                if key == "iss" && value == "issuer-c"
+        ));
+
+        let claims = Claims::new().with_issuer("issuer-a").with_subject("user");
+        assert!(matches!(
+            validation.validate(&claims),
+            Err(TokenError::Validation(ValidationError::MissingClaim(key))) if key == "aud"
+        ));
+    }
+
+    #[test]
+    fn test_max_age() {
+        let now = Utc::now();
+        let validation = Validation::new().with_max_age(Duration::minutes(5));
+
+        let claims = claims_at(reserved::ISSUED_AT, now - Duration::minutes(1));
+        assert!(validation.validate_at(&claims, now).is_ok());
+
+        let claims = claims_at(reserved::ISSUED_AT, now - Duration::minutes(10));
+        assert!(matches!(
+            validation.validate_at(&claims, now),
+            Err(TokenError::Validation(ValidationError::TooOld))
+        ));
+
+        assert!(matches!(
+            validation.validate_at(&Claims::new(), now),
+            Err(TokenError::Validation(ValidationError::MissingClaim(_)))
+        ));
+    }
+}
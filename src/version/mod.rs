@@ -1,3 +1,6 @@
-pub trait Version {}
+pub trait Version {
+    /// The version part of a token header, e.g. `v4`.
+    const NAME: &'static str;
+}
 mod v4;
 pub use v4::V4;
@@ -4,4 +4,6 @@ pub struct V4;
 impl V4 {
     pub const NAME: &'static str = "v4";
 }
-impl Version for V4 {}
+impl Version for V4 {
+    const NAME: &'static str = V4::NAME;
+}